@@ -0,0 +1,186 @@
+//! Plays every answer in a dictionary with a solver strategy and reports how
+//! it did, to compare strategies, openers and word lists.
+//!
+//! Rounds are played through `Game`, so the scoring, word list checks and
+//! hard mode rules are the ones the game itself uses.
+
+mod strategy;
+
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+use birdle_core::{Config, Dictionary, Game, Stats, Status};
+use strategy::Strategy;
+
+const USAGE: &str = "\
+usage: birdle-bench [options]
+
+options:
+  --dict PATH        dictionary to play (default: assets/words.dict)
+  --strategy NAME    entropy or first (default: entropy)
+  --opener WORD      always open with WORD
+  --length N         letters per word, 4 to 8 (default: 5)
+  --guesses N        guesses per round (default: 6)
+  --hard             play in hard mode
+  --limit N          only play the first N answers";
+
+/// Width of the longest bar in the distribution chart.
+const BAR_WIDTH: u32 = 50;
+
+struct Options {
+    dict: PathBuf,
+    strategy: String,
+    opener: Option<String>,
+    config: Config,
+    limit: Option<usize>,
+}
+
+/// A round the strategy didn't win, and why.
+struct Failure {
+    answer: String,
+    reason: String,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    let dictionary = match Dictionary::open(&options.dict) {
+        Ok(dictionary) => dictionary,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    let mut strategy = match strategy::by_name(&options.strategy, options.opener.clone()) {
+        Some(strategy) => strategy,
+        None => {
+            eprintln!("unknown strategy '{}', expected one of: {}", options.strategy, strategy::NAMES.join(", "));
+            process::exit(2);
+        }
+    };
+
+    let answers: Vec<&String> = dictionary
+        .answers()
+        .iter()
+        .filter(|word| options.config.fits(word))
+        .take(options.limit.unwrap_or(usize::MAX))
+        .collect();
+    if answers.is_empty() {
+        eprintln!("No {}-letter answers in {}", options.config.word_length, options.dict.display());
+        process::exit(1);
+    }
+
+    let started = Instant::now();
+    let mut stats = Stats::default();
+    let mut failures = Vec::new();
+    for answer in &answers {
+        match play(strategy.as_mut(), &dictionary, answer, options.config) {
+            Ok(game) => stats.record(&game),
+            Err(reason) => {
+                stats.record_loss();
+                failures.push(Failure {
+                    answer: answer.to_string(),
+                    reason,
+                });
+            }
+        }
+    }
+    report(&options, strategy.as_ref(), &stats, &failures);
+    println!("took {:.1?}", started.elapsed());
+}
+
+/// Plays one round to the end. A round that isn't won comes back as the
+/// reason it was lost.
+fn play(strategy: &mut dyn Strategy, dictionary: &Dictionary, answer: &str, config: Config) -> Result<Game, String> {
+    let mut game = Game::new(answer, config);
+    while game.status() == Status::InProgress {
+        let guess = strategy
+            .next_guess(dictionary, &game)
+            .ok_or_else(|| "gave up".to_string())?;
+        game.submit(&guess, dictionary)
+            .map_err(|err| format!("guessed {}: {}", guess, err))?;
+    }
+    match game.status() {
+        Status::Won => Ok(game),
+        _ => Err(format!(
+            "ran out of guesses: {}",
+            game.guesses().iter().map(|guess| guess.word.as_str()).collect::<Vec<_>>().join(" ")
+        )),
+    }
+}
+
+fn report(options: &Options, strategy: &dyn Strategy, stats: &Stats, failures: &[Failure]) {
+    let config = options.config;
+    println!(
+        "{} on {}: {} letters, {} guesses{}",
+        strategy.name(),
+        options.dict.display(),
+        config.word_length,
+        config.max_guesses,
+        if config.hard_mode { ", hard mode" } else { "" }
+    );
+    println!("won {}/{} ({}%)", stats.wins, stats.played, stats.win_percentage());
+
+    let total: u32 = stats.distribution.iter().enumerate().map(|(i, &count)| (i as u32 + 1) * count).sum();
+    let worst = stats.distribution.iter().rposition(|&count| count > 0).map_or(0, |i| i + 1);
+    if stats.wins > 0 {
+        println!("average {:.3} guesses, worst {}", total as f64 / stats.wins as f64, worst);
+    }
+
+    let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+    for (i, &count) in stats.distribution.iter().enumerate() {
+        let bar = "#".repeat((count * BAR_WIDTH / most) as usize);
+        println!("{:>3} | {} {}", i + 1, bar, count);
+    }
+
+    if !failures.is_empty() {
+        println!("\n{} failures:", failures.len());
+        for failure in failures {
+            println!("  {}: {}", failure.answer, failure.reason);
+        }
+    }
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        dict: PathBuf::from("assets/words.dict"),
+        strategy: "entropy".to_string(),
+        opener: None,
+        config: Config::default(),
+        limit: None,
+    };
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--dict" => options.dict = PathBuf::from(value("--dict")?),
+            "--strategy" => options.strategy = value("--strategy")?,
+            "--opener" => options.opener = Some(value("--opener")?.to_lowercase()),
+            "--length" => {
+                options.config.word_length =
+                    Config::parse_word_length(&value("--length")?).map_err(|err| err.to_string())?
+            }
+            "--guesses" => {
+                options.config.max_guesses =
+                    Config::parse_max_guesses(&value("--guesses")?).map_err(|err| err.to_string())?
+            }
+            "--hard" => options.config.hard_mode = true,
+            "--limit" => {
+                let limit = value("--limit")?;
+                options.limit = Some(limit.parse().map_err(|_| format!("invalid limit '{}'", limit))?);
+            }
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("unknown option '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
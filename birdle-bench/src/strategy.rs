@@ -0,0 +1,82 @@
+use birdle_core::solver::Solver;
+use birdle_core::{Dictionary, Game};
+
+/// A way of picking guesses, for the benchmark to play every answer with.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// The next word to guess in `game`, or `None` to give up.
+    fn next_guess(&mut self, dictionary: &Dictionary, game: &Game) -> Option<String>;
+}
+
+/// Every strategy the benchmark knows, by the name `--strategy` takes.
+pub fn by_name(name: &str, opener: Option<String>) -> Option<Box<dyn Strategy>> {
+    match name {
+        "entropy" => Some(Box::new(MaxEntropy::new(opener))),
+        "first" => Some(Box::new(FirstCandidate::new(opener))),
+        _ => None,
+    }
+}
+
+pub const NAMES: [&str; 2] = ["entropy", "first"];
+
+/// Guesses whatever the solver ranks highest.
+pub struct MaxEntropy {
+    /// The first guess never depends on the answer, so it's only worked out once.
+    opener: Option<String>,
+}
+
+impl MaxEntropy {
+    pub fn new(opener: Option<String>) -> Self {
+        MaxEntropy { opener }
+    }
+}
+
+impl Strategy for MaxEntropy {
+    fn name(&self) -> String {
+        match &self.opener {
+            Some(opener) => format!("entropy, opening with {}", opener),
+            None => "entropy".to_string(),
+        }
+    }
+
+    fn next_guess(&mut self, dictionary: &Dictionary, game: &Game) -> Option<String> {
+        if game.guesses().is_empty() {
+            if self.opener.is_none() {
+                self.opener = Solver::new(dictionary, *game.config()).suggest(1).pop().map(|s| s.word);
+            }
+            return self.opener.clone();
+        }
+        Solver::for_game(dictionary, game).suggest(1).pop().map(|s| s.word)
+    }
+}
+
+/// Guesses the first answer still possible, as a baseline to compare against.
+pub struct FirstCandidate {
+    opener: Option<String>,
+}
+
+impl FirstCandidate {
+    pub fn new(opener: Option<String>) -> Self {
+        FirstCandidate { opener }
+    }
+}
+
+impl Strategy for FirstCandidate {
+    fn name(&self) -> String {
+        match &self.opener {
+            Some(opener) => format!("first candidate, opening with {}", opener),
+            None => "first candidate".to_string(),
+        }
+    }
+
+    fn next_guess(&mut self, dictionary: &Dictionary, game: &Game) -> Option<String> {
+        if game.guesses().is_empty() && self.opener.is_some() {
+            return self.opener.clone();
+        }
+        Solver::for_game(dictionary, game)
+            .candidates()
+            .first()
+            .map(|word| word.to_string())
+    }
+}
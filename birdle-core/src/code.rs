@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::rng::SplitMix64;
+
+/// Crockford's base 32 alphabet: no I, L, O or U to misread.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_LENGTH: usize = 7;
+/// Odd multiplier that scrambles the payload so codes don't reveal the index.
+const SCRAMBLE: u32 = 0x2c1b_3c6d;
+const SCRAMBLE_MASK: u32 = 0x5bd1_e995;
+
+/// A short, shareable name for one puzzle.
+///
+/// The code carries the word length and the answer's index in the list of
+/// answers of that length. Both are scrambled, so the code doesn't give the
+/// word away, but the same code always names the same answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleCode {
+    pub word_length: usize,
+    pub index: usize,
+}
+
+/// Why a string is not a valid puzzle code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    WrongLength(usize),
+    InvalidCharacter(char),
+    BadChecksum,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeError::WrongLength(found) => {
+                write!(f, "puzzle codes have {} characters, got {}", CODE_LENGTH, found)
+            }
+            CodeError::InvalidCharacter(c) => write!(f, "'{}' can't appear in a puzzle code", c),
+            CodeError::BadChecksum => write!(f, "puzzle code is mistyped"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+impl PuzzleCode {
+    pub fn new(word_length: usize, index: usize) -> Self {
+        PuzzleCode { word_length, index }
+    }
+
+    fn payload(&self) -> u32 {
+        ((self.index as u32) << 4) | (self.word_length as u32 & 0xf)
+    }
+
+    fn from_payload(payload: u32) -> Self {
+        PuzzleCode {
+            word_length: (payload & 0xf) as usize,
+            index: (payload >> 4) as usize,
+        }
+    }
+}
+
+fn scramble(x: u32) -> u32 {
+    let x = (x ^ SCRAMBLE_MASK).wrapping_mul(SCRAMBLE);
+    x ^ (x >> 16)
+}
+
+fn unscramble(x: u32) -> u32 {
+    let x = x ^ (x >> 16);
+    // Newton's iteration for the inverse of an odd number mod 2^32.
+    let mut inverse = SCRAMBLE;
+    for _ in 0..4 {
+        inverse = inverse.wrapping_mul(2u32.wrapping_sub(SCRAMBLE.wrapping_mul(inverse)));
+    }
+    x.wrapping_mul(inverse) ^ SCRAMBLE_MASK
+}
+
+fn checksum(x: u32) -> u64 {
+    (x.count_ones() + (x >> 29)) as u64 & 0b111
+}
+
+impl fmt::Display for PuzzleCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scrambled = scramble(self.payload());
+        // 7 characters hold 35 bits: the scrambled payload and a 3 bit checksum.
+        let mut bits = ((scrambled as u64) << 3) | checksum(scrambled);
+        let mut code = [0u8; CODE_LENGTH];
+        for c in code.iter_mut().rev() {
+            *c = ALPHABET[(bits & 0x1f) as usize];
+            bits >>= 5;
+        }
+        write!(f, "{}", std::str::from_utf8(&code).expect("Alphabet is ASCII"))
+    }
+}
+
+impl FromStr for PuzzleCode {
+    type Err = CodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let found = s.chars().filter(|c| *c != '-').count();
+        if found != CODE_LENGTH {
+            return Err(CodeError::WrongLength(found));
+        }
+
+        let mut bits: u64 = 0;
+        for c in s.chars().filter(|c| *c != '-') {
+            let normalised = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                other => other,
+            };
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a as char == normalised)
+                .ok_or(CodeError::InvalidCharacter(c))?;
+            bits = (bits << 5) | value as u64;
+        }
+
+        let scrambled = (bits >> 3) as u32;
+        if bits & 0b111 != checksum(scrambled) {
+            return Err(CodeError::BadChecksum);
+        }
+        Ok(PuzzleCode::from_payload(unscramble(scrambled)))
+    }
+}
+
+/// The answer index a numeric seed picks out of `len` answers.
+pub fn seeded_index(seed: u64, len: usize) -> Option<usize> {
+    if len == 0 {
+        None
+    } else {
+        Some(SplitMix64::new(seed).below(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        for word_length in 4..=8 {
+            for index in [0, 1, 42, 2314, 65_535, (1 << 28) - 1] {
+                let code = PuzzleCode::new(word_length, index);
+                assert_eq!(code.to_string().parse(), Ok(code), "{}", code);
+            }
+        }
+    }
+
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(PuzzleCode::new(5, 0).to_string(), "NN79Y7G");
+        assert_eq!(PuzzleCode::new(5, 1).to_string(), "XED806N");
+        assert_eq!(PuzzleCode::new(4, 1000).to_string(), "W65PZDJ");
+        assert_eq!(PuzzleCode::new(8, (1 << 28) - 1).to_string(), "SSGDC2C");
+    }
+
+    #[test]
+    fn lookalike_letters_are_read_as_digits() {
+        assert_eq!("xed8o6n".parse(), Ok(PuzzleCode::new(5, 1)));
+        assert_eq!("XED-8O6-N".parse(), Ok(PuzzleCode::new(5, 1)));
+        assert_eq!("6Y2ESDI".parse(), Ok(PuzzleCode::new(5, 6)));
+        assert_eq!("TlEJXQ8".parse(), Ok(PuzzleCode::new(5, 17)));
+    }
+
+    #[test]
+    fn mistyped_codes_are_rejected() {
+        assert_eq!("NN79Y7H".parse::<PuzzleCode>(), Err(CodeError::BadChecksum));
+        assert_eq!("NN79Y7".parse::<PuzzleCode>(), Err(CodeError::WrongLength(6)));
+        assert_eq!("NN79Y7U".parse::<PuzzleCode>(), Err(CodeError::InvalidCharacter('U')));
+    }
+
+    #[test]
+    fn seeds_pick_the_same_index_everywhere() {
+        assert_eq!(seeded_index(0, 100), Some(35));
+        assert_eq!(seeded_index(1, 100), Some(65));
+        assert_eq!(seeded_index(42, 2315), Some(1638));
+        assert_eq!(seeded_index(20_220_101, 2315), Some(944));
+        assert_eq!(seeded_index(42, 0), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+/// Shortest word length a game can be played with.
+pub const MIN_WORD_LENGTH: usize = 4;
+/// Longest word length a game can be played with.
+pub const MAX_WORD_LENGTH: usize = 8;
+
+/// The parameters a round is played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    /// Number of letters in the answer and in every guess.
+    pub word_length: usize,
+    /// Number of guesses a player gets before the round is lost.
+    pub max_guesses: usize,
+    /// Whether every guess must reuse the hints revealed so far.
+    pub hard_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            word_length: 5,
+            max_guesses: 6,
+            hard_mode: false,
+        }
+    }
+}
+
+/// A setting given as text, such as a command line option, that can't be
+/// played with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    InvalidWordLength(String),
+    InvalidMaxGuesses(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::InvalidWordLength(text) => write!(f, "invalid word length '{}'", text),
+            ConfigError::InvalidMaxGuesses(text) => write!(f, "invalid number of guesses '{}'", text),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Whether `word` can be an answer or a guess under this config.
+    pub fn fits(&self, word: &str) -> bool {
+        word.chars().count() == self.word_length
+    }
+
+    /// Whether a game can be played with words `length` letters long.
+    pub fn is_playable_length(length: usize) -> bool {
+        (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length)
+    }
+
+    /// Reads a word length, which must be from [`MIN_WORD_LENGTH`] to
+    /// [`MAX_WORD_LENGTH`].
+    pub fn parse_word_length(text: &str) -> Result<usize, ConfigError> {
+        match text.parse() {
+            Ok(length) if Config::is_playable_length(length) => Ok(length),
+            _ => Err(ConfigError::InvalidWordLength(text.to_string())),
+        }
+    }
+
+    /// Reads a number of guesses, which must be at least one.
+    pub fn parse_max_guesses(text: &str) -> Result<usize, ConfigError> {
+        match text.parse() {
+            Ok(guesses) if guesses > 0 => Ok(guesses),
+            _ => Err(ConfigError::InvalidMaxGuesses(text.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_lengths_must_be_playable() {
+        assert_eq!(Config::parse_word_length("4"), Ok(4));
+        assert_eq!(Config::parse_word_length("8"), Ok(8));
+        for text in ["3", "9", "five", ""] {
+            assert_eq!(
+                Config::parse_word_length(text),
+                Err(ConfigError::InvalidWordLength(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn there_must_be_a_guess() {
+        assert_eq!(Config::parse_max_guesses("1"), Ok(1));
+        assert_eq!(
+            Config::parse_max_guesses("0"),
+            Err(ConfigError::InvalidMaxGuesses("0".to_string()))
+        );
+        assert_eq!(
+            Config::parse_max_guesses("-1"),
+            Err(ConfigError::InvalidMaxGuesses("-1".to_string()))
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::rng::SplitMix64;
+
+/// Seed for the order daily answers are handed out in. Changing it changes
+/// every past and future daily puzzle.
+const ORDER_SEED: u64 = 0xb1d1_e000_0000_0001;
+
+/// A calendar date, as seen by the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The date of daily puzzle number 0.
+pub const FIRST_PUZZLE: Date = Date {
+    year: 2022,
+    month: 1,
+    day: 1,
+};
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Date { year, month, day }
+    }
+
+    /// Days since 1970-01-01 in the proleptic Gregorian calendar.
+    fn days_since_epoch(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+/// The daily puzzle number for `date`, or `None` before the first puzzle.
+pub fn puzzle_number(date: Date) -> Option<u32> {
+    let days = date.days_since_epoch() - FIRST_PUZZLE.days_since_epoch();
+    u32::try_from(days).ok()
+}
+
+/// The index of the answer for daily puzzle `number` in a list of `len` answers.
+///
+/// Answers are dealt from a fixed shuffle of the list, so every player with
+/// the same list gets the same word on the same day.
+pub fn daily_index(len: usize, number: u32) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut rng = SplitMix64::new(ORDER_SEED);
+    for i in (1..len).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+    Some(order[number as usize % len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzles_are_numbered_from_the_first_day() {
+        assert_eq!(puzzle_number(FIRST_PUZZLE), Some(0));
+        assert_eq!(puzzle_number(Date::new(2022, 3, 1)), Some(59));
+        assert_eq!(puzzle_number(Date::new(2023, 1, 1)), Some(365));
+        assert_eq!(puzzle_number(Date::new(2021, 12, 31)), None);
+    }
+
+    #[test]
+    fn daily_answers_are_the_same_everywhere() {
+        assert_eq!(daily_index(100, 0), Some(7));
+        assert_eq!(daily_index(100, 1), Some(95));
+        assert_eq!(daily_index(2315, 0), Some(1879));
+        assert_eq!(daily_index(2315, 100), Some(315));
+        assert_eq!(daily_index(2315, 365), Some(1760));
+        assert_eq!(daily_index(0, 0), None);
+    }
+}
@@ -0,0 +1,677 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The first line of a versioned dictionary starts with this, followed by
+/// the format version.
+pub const FORMAT_MAGIC: &str = "birdle-dict";
+/// The newest dictionary format this crate reads.
+pub const FORMAT_VERSION: u32 = 1;
+
+const ENGLISH_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// Optional per-word attributes from a dictionary file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordInfo {
+    /// How common the word is, on whatever scale the file uses.
+    pub frequency: Option<f64>,
+    pub tags: Vec<String>,
+}
+
+/// The words a game is played with.
+///
+/// `answers` is the curated pool answers are drawn from. Any answer may also
+/// be guessed, along with the usually much larger `allowed` list.
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    language: String,
+    alphabet: Vec<char>,
+    word_length: Option<usize>,
+    /// Whether the alphabet and length come from a header rather than the words.
+    versioned: bool,
+    answers: Vec<String>,
+    allowed: HashSet<String>,
+    info: HashMap<String, WordInfo>,
+}
+
+/// A dictionary file that could not be read, and where.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DictionaryError {
+    /// One-based line number the problem was found on.
+    pub line: usize,
+    pub kind: DictionaryErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictionaryErrorKind {
+    UnsupportedVersion(String),
+    MissingHeader(&'static str),
+    UnknownHeader(String),
+    InvalidHeader { key: String, value: String },
+    UnknownSection(String),
+    InvalidLetter { word: String, letter: char },
+    WrongLength { word: String, expected: usize },
+    DuplicateWord(String),
+    UnknownAttribute(String),
+    InvalidAttribute { key: String, value: String },
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            DictionaryErrorKind::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported format version '{}' (expected up to {})",
+                version, FORMAT_VERSION
+            ),
+            DictionaryErrorKind::MissingHeader(key) => write!(f, "missing '{}' header", key),
+            DictionaryErrorKind::UnknownHeader(key) => write!(f, "unknown header '{}'", key),
+            DictionaryErrorKind::InvalidHeader { key, value } => {
+                write!(f, "invalid value '{}' for header '{}'", value, key)
+            }
+            DictionaryErrorKind::UnknownSection(section) => write!(f, "unknown section '{}'", section),
+            DictionaryErrorKind::InvalidLetter { word, letter } => {
+                write!(f, "'{}' contains '{}', which is not in the alphabet", word, letter)
+            }
+            DictionaryErrorKind::WrongLength { word, expected } => {
+                write!(f, "'{}' is not {} letters long", word, expected)
+            }
+            DictionaryErrorKind::DuplicateWord(word) => write!(f, "'{}' is listed twice", word),
+            DictionaryErrorKind::UnknownAttribute(key) => write!(f, "unknown attribute '{}'", key),
+            DictionaryErrorKind::InvalidAttribute { key, value } => {
+                write!(f, "invalid value '{}' for attribute '{}'", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+/// A dictionary file on disk that could not be opened, and which one.
+#[derive(Debug)]
+pub struct OpenError {
+    pub path: PathBuf,
+    pub kind: OpenErrorKind,
+}
+
+#[derive(Debug)]
+pub enum OpenErrorKind {
+    Read(io::Error),
+    Invalid(DictionaryError),
+}
+
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            OpenErrorKind::Read(err) => write!(f, "Could not read {}: {}", self.path.display(), err),
+            OpenErrorKind::Invalid(err) => write!(f, "Invalid dictionary {}: {}", self.path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for OpenError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Answers,
+    Allowed,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary {
+            language: "en".to_string(),
+            alphabet: ENGLISH_ALPHABET.chars().collect(),
+            word_length: None,
+            versioned: false,
+            answers: Vec::new(),
+            allowed: HashSet::new(),
+            info: HashMap::new(),
+        }
+    }
+}
+
+impl Dictionary {
+    /// An English dictionary built from plain word lists.
+    pub fn new(answers: Vec<String>, allowed: impl IntoIterator<Item = String>) -> Self {
+        let mut allowed: HashSet<String> = allowed.into_iter().collect();
+        allowed.extend(answers.iter().cloned());
+        Dictionary {
+            answers,
+            allowed,
+            ..Dictionary::default()
+        }
+    }
+
+    /// Reads a dictionary file.
+    ///
+    /// A versioned file starts with `birdle-dict 1`, then `key: value` headers
+    /// for `language`, `alphabet` and optionally `length`. Each following line
+    /// is a word, optionally followed by `freq=`, `answer=yes|no` and
+    /// `tags=a,b` attributes. Words after an `[allowed]` header are guess-only
+    /// unless marked `answer=yes`; words before it, or after `[answers]`, are
+    /// answers. Lines starting with `#` are comments.
+    ///
+    /// A file without the `birdle-dict` line is read as a bare word list in
+    /// the same sections, with its alphabet taken from the words it holds.
+    pub fn parse(text: &str) -> Result<Self, DictionaryError> {
+        let mut dictionary = Dictionary::default();
+        dictionary.read(text, true)?;
+        Ok(dictionary)
+    }
+
+    /// Reads the words of one file into this dictionary.
+    ///
+    /// Only the `first` file sets the header. Later files are read as though
+    /// they followed an `[allowed]` header, and their words are held to the
+    /// first file's alphabet and length.
+    fn read(&mut self, text: &str, first: bool) -> Result<(), DictionaryError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        let versioned = match lines.peek() {
+            Some((_, line)) => line.split_whitespace().next() == Some(FORMAT_MAGIC),
+            None => false,
+        };
+        if versioned {
+            let (line, magic) = lines.next().expect("Peeked line");
+            let version = magic[FORMAT_MAGIC.len()..].trim();
+            match version.parse::<u32>() {
+                Ok(v) if (1..=FORMAT_VERSION).contains(&v) => {}
+                _ => return Err(error(line, DictionaryErrorKind::UnsupportedVersion(version.to_string()))),
+            }
+            if first {
+                self.parse_header(line, &mut lines)?;
+                self.versioned = true;
+            } else {
+                Dictionary::default().parse_header(line, &mut lines)?;
+            }
+        }
+
+        let mut section = if first { Section::Answers } else { Section::Allowed };
+        for (line, text) in lines {
+            if text.starts_with('[') {
+                section = match text {
+                    "[answers]" => Section::Answers,
+                    "[allowed]" => Section::Allowed,
+                    _ => return Err(error(line, DictionaryErrorKind::UnknownSection(text.to_string()))),
+                };
+            } else if versioned {
+                self.parse_entry(line, text, section)?;
+            } else {
+                self.parse_bare_word(line, text, section)?;
+            }
+        }
+
+        if !self.versioned {
+            let letters: BTreeSet<char> = self.allowed.iter().flat_map(|word| word.chars()).collect();
+            self.alphabet = letters.into_iter().collect();
+        }
+        Ok(())
+    }
+
+    fn parse_header<'a>(
+        &mut self,
+        magic_line: usize,
+        lines: &mut std::iter::Peekable<impl Iterator<Item = (usize, &'a str)>>,
+    ) -> Result<(), DictionaryError> {
+        let mut language = None;
+        let mut alphabet = None;
+        while let Some(&(line, text)) = lines.peek() {
+            let (key, value) = match text.split_once(':') {
+                Some((key, value)) if !text.starts_with('[') => (key.trim(), value.trim()),
+                _ => break,
+            };
+            lines.next();
+            let invalid = || {
+                error(
+                    line,
+                    DictionaryErrorKind::InvalidHeader {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    },
+                )
+            };
+            match key {
+                "language" if !value.is_empty() => language = Some(value.to_string()),
+                "alphabet" => {
+                    let letters: Vec<char> = value.chars().collect();
+                    let unique: HashSet<&char> = letters.iter().collect();
+                    if letters.is_empty() || unique.len() != letters.len() || !letters.iter().all(|c| c.is_alphabetic()) {
+                        return Err(invalid());
+                    }
+                    alphabet = Some(letters);
+                }
+                "length" => self.word_length = Some(value.parse().map_err(|_| invalid())?),
+                "language" => return Err(invalid()),
+                _ => return Err(error(line, DictionaryErrorKind::UnknownHeader(key.to_string()))),
+            }
+        }
+        self.language = language.ok_or_else(|| error(magic_line, DictionaryErrorKind::MissingHeader("language")))?;
+        self.alphabet = alphabet.ok_or_else(|| error(magic_line, DictionaryErrorKind::MissingHeader("alphabet")))?;
+        Ok(())
+    }
+
+    fn parse_entry(&mut self, line: usize, text: &str, section: Section) -> Result<(), DictionaryError> {
+        let mut fields = text.split_whitespace();
+        let word = fields.next().expect("Line is not empty");
+        self.check_word(line, word)?;
+
+        let mut is_answer = section == Section::Answers;
+        let mut info = WordInfo::default();
+        for field in fields {
+            let (key, value) = field.split_once('=').unwrap_or((field, ""));
+            let invalid = || {
+                error(
+                    line,
+                    DictionaryErrorKind::InvalidAttribute {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    },
+                )
+            };
+            match key {
+                "freq" => match value.parse::<f64>() {
+                    Ok(frequency) if frequency.is_finite() && frequency >= 0. => info.frequency = Some(frequency),
+                    _ => return Err(invalid()),
+                },
+                "answer" => {
+                    is_answer = match value {
+                        "yes" | "true" => true,
+                        "no" | "false" => false,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "tags" => {
+                    if value.is_empty() || value.split(',').any(str::is_empty) {
+                        return Err(invalid());
+                    }
+                    info.tags = value.split(',').map(str::to_string).collect();
+                }
+                _ => return Err(error(line, DictionaryErrorKind::UnknownAttribute(key.to_string()))),
+            }
+        }
+
+        self.insert(line, word, is_answer)?;
+        if info != WordInfo::default() {
+            self.info.insert(word.to_string(), info);
+        }
+        Ok(())
+    }
+
+    fn parse_bare_word(&mut self, line: usize, word: &str, section: Section) -> Result<(), DictionaryError> {
+        self.check_word(line, word)?;
+        self.insert(line, word, section == Section::Answers)
+    }
+
+    /// Checks `word` against the header's alphabet and length, or only that
+    /// it is made of letters if there is no header.
+    fn check_word(&self, line: usize, word: &str) -> Result<(), DictionaryError> {
+        let is_letter = |c: &char| match self.versioned {
+            true => self.alphabet.contains(c),
+            false => c.is_alphabetic(),
+        };
+        if let Some(letter) = word.chars().find(|c| !is_letter(c)) {
+            return Err(error(
+                line,
+                DictionaryErrorKind::InvalidLetter {
+                    word: word.to_string(),
+                    letter,
+                },
+            ));
+        }
+        if let Some(expected) = self.word_length {
+            if word.chars().count() != expected {
+                return Err(error(
+                    line,
+                    DictionaryErrorKind::WrongLength {
+                        word: word.to_string(),
+                        expected,
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, line: usize, word: &str, is_answer: bool) -> Result<(), DictionaryError> {
+        if !self.allowed.insert(word.to_string()) {
+            return Err(error(line, DictionaryErrorKind::DuplicateWord(word.to_string())));
+        }
+        if is_answer {
+            self.answers.push(word.to_string());
+        }
+        Ok(())
+    }
+
+    /// Reads an answer list and a separate list of extra allowed guesses.
+    ///
+    /// The header comes from the answer list. The second file reads as though
+    /// it followed the answer list's `[allowed]` header: its words are
+    /// guess-only, must fit the same alphabet and length, and may not repeat
+    /// a word from the answer list.
+    pub fn parse_pair(answers: &str, allowed: &str) -> Result<Self, DictionaryError> {
+        let mut dictionary = Dictionary::parse(answers)?;
+        dictionary.read(allowed, false)?;
+        Ok(dictionary)
+    }
+
+    /// Reads `main`, the dictionary file at `path`, paired with `allowed`,
+    /// the contents of its [`allowed_path`] file, if there is one.
+    ///
+    /// Errors name the file they were found in.
+    pub fn parse_files(path: &Path, main: &str, allowed: Option<&str>) -> Result<Self, OpenError> {
+        let mut dictionary = Dictionary::parse(main).map_err(|err| invalid_file(path, err))?;
+        if let Some(allowed) = allowed {
+            dictionary
+                .read(allowed, false)
+                .map_err(|err| invalid_file(&allowed_path(path), err))?;
+        }
+        Ok(dictionary)
+    }
+
+    /// Reads the dictionary file at `path`, paired with its [`allowed_path`]
+    /// file if there is one.
+    pub fn open(path: &Path) -> Result<Self, OpenError> {
+        let main = read_file(path)?;
+        let allowed = allowed_path(path);
+        let allowed = if allowed.exists() { Some(read_file(&allowed)?) } else { None };
+        Dictionary::parse_files(path, &main, allowed.as_deref())
+    }
+
+    /// The language code from the file header, `en` for bare word lists.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Every letter words in this dictionary may use.
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    /// The length every word has, if the file declares one.
+    pub fn word_length(&self) -> Option<usize> {
+        self.word_length
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Every word that can be guessed, answers included.
+    pub fn allowed(&self) -> impl Iterator<Item = &String> {
+        self.allowed.iter()
+    }
+
+    pub fn is_allowed(&self, word: &str) -> bool {
+        self.allowed.contains(word)
+    }
+
+    /// The attributes listed for `word`, if it has any.
+    pub fn info(&self, word: &str) -> Option<&WordInfo> {
+        self.info.get(word)
+    }
+}
+
+/// The file a dictionary at `path` takes extra allowed guesses from:
+/// `words.dict` is paired with `words.allowed`.
+pub fn allowed_path(path: &Path) -> PathBuf {
+    path.with_extension("allowed")
+}
+
+fn read_file(path: &Path) -> Result<String, OpenError> {
+    std::fs::read_to_string(path).map_err(|err| OpenError {
+        path: path.to_path_buf(),
+        kind: OpenErrorKind::Read(err),
+    })
+}
+
+fn invalid_file(path: &Path, err: DictionaryError) -> OpenError {
+    OpenError {
+        path: path.to_path_buf(),
+        kind: OpenErrorKind::Invalid(err),
+    }
+}
+
+fn error(line: usize, kind: DictionaryErrorKind) -> DictionaryError {
+    DictionaryError { line, kind }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "birdle-dict 1\nlanguage: xx\nalphabet: abcde\nlength: 3\n";
+
+    fn parse_error(text: &str) -> (usize, DictionaryErrorKind) {
+        let err = Dictionary::parse(text).unwrap_err();
+        (err.line, err.kind)
+    }
+
+    fn with_words(words: &str) -> String {
+        format!("{}\n# a comment\n[answers]\n{}", HEADER, words)
+    }
+
+    #[test]
+    fn reads_sections_and_attributes() {
+        let dictionary = Dictionary::parse(&with_words("cab freq=0.5 tags=common\nbad\n[allowed]\nace\ndab answer=yes")).unwrap();
+        assert_eq!(dictionary.language(), "xx");
+        assert_eq!(dictionary.word_length(), Some(3));
+        assert_eq!(dictionary.answers(), ["cab", "bad", "dab"]);
+        assert!(dictionary.is_allowed("ace"));
+        assert_eq!(dictionary.info("cab").unwrap().frequency, Some(0.5));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        assert_eq!(
+            parse_error("birdle-dict 2\nlanguage: xx\nalphabet: abc\n"),
+            (1, DictionaryErrorKind::UnsupportedVersion("2".to_string()))
+        );
+    }
+
+    #[test]
+    fn requires_a_language() {
+        assert_eq!(
+            parse_error("birdle-dict 1\nalphabet: abc\n[answers]\ncab\n"),
+            (1, DictionaryErrorKind::MissingHeader("language"))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        let invalid = |key: &str, value: &str| DictionaryErrorKind::InvalidHeader {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        assert_eq!(parse_error("birdle-dict 1\nlanguage: xx\nalphabet: abca\n"), (3, invalid("alphabet", "abca")));
+        assert_eq!(parse_error("birdle-dict 1\nlanguage: xx\nalphabet: ab1\n"), (3, invalid("alphabet", "ab1")));
+        assert_eq!(parse_error("birdle-dict 1\nlanguage: xx\nlength: five\n"), (3, invalid("length", "five")));
+        assert_eq!(
+            parse_error("birdle-dict 1\nlanguage: xx\nscript: latin\n"),
+            (3, DictionaryErrorKind::UnknownHeader("script".to_string()))
+        );
+    }
+
+    #[test]
+    fn reads_alphabets_beyond_a_to_z() {
+        let german = "birdle-dict 1\nlanguage: de\nalphabet: abcdefghijklmnopqrstuvwxyzäöüß\n[answers]\nbären\nfüße\n";
+        let german = Dictionary::parse(german).unwrap();
+        assert_eq!(german.answers(), ["bären", "füße"]);
+        assert_eq!(german.alphabet().len(), 30);
+        let polish = "birdle-dict 1\nlanguage: pl\nalphabet: abcdefghijklmnoprstuwyzł\nlength: 5\n[answers]\nbiały\n";
+        assert!(Dictionary::parse(polish).unwrap().is_allowed("biały"));
+    }
+
+    #[test]
+    fn rejects_unknown_sections() {
+        assert_eq!(
+            parse_error(&with_words("cab\n[banned]\n")),
+            (9, DictionaryErrorKind::UnknownSection("[banned]".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_letters_outside_the_alphabet() {
+        assert_eq!(
+            parse_error(&with_words("cab\nfab\n")),
+            (
+                9,
+                DictionaryErrorKind::InvalidLetter {
+                    word: "fab".to_string(),
+                    letter: 'f',
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_words_of_the_wrong_length() {
+        assert_eq!(
+            parse_error(&with_words("cab\nabed\n")),
+            (
+                9,
+                DictionaryErrorKind::WrongLength {
+                    word: "abed".to_string(),
+                    expected: 3,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_words() {
+        assert_eq!(
+            parse_error(&with_words("cab\n[allowed]\ncab\n")),
+            (10, DictionaryErrorKind::DuplicateWord("cab".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_attributes() {
+        assert_eq!(
+            parse_error(&with_words("cab colour=red\n")),
+            (8, DictionaryErrorKind::UnknownAttribute("colour".to_string()))
+        );
+        for freq in ["lots", "-1", "inf"] {
+            assert_eq!(
+                parse_error(&with_words(&format!("cab freq={}\n", freq))),
+                (
+                    8,
+                    DictionaryErrorKind::InvalidAttribute {
+                        key: "freq".to_string(),
+                        value: freq.to_string(),
+                    }
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn bare_word_lists_take_their_alphabet_from_the_words() {
+        let dictionary = Dictionary::parse("cab\n[allowed]\nbad\n").unwrap();
+        assert_eq!(dictionary.alphabet(), ['a', 'b', 'c', 'd']);
+        assert_eq!(dictionary.answers(), ["cab"]);
+        assert_eq!(
+            parse_error("cab\nca-b\n"),
+            (
+                2,
+                DictionaryErrorKind::InvalidLetter {
+                    word: "ca-b".to_string(),
+                    letter: '-',
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn allowed_files_add_guess_only_words() {
+        let path = Path::new("words.dict");
+        let dictionary = Dictionary::parse_files(path, &with_words("cab\n"), Some("bad\nace\n")).unwrap();
+        assert_eq!(dictionary.answers(), ["cab"]);
+        assert!(dictionary.is_allowed("bad") && dictionary.is_allowed("ace"));
+        let alone = Dictionary::parse_files(path, &with_words("cab\n"), None).unwrap();
+        assert!(!alone.is_allowed("bad"));
+    }
+
+    #[test]
+    fn errors_name_the_file_they_are_in() {
+        let path = Path::new("words.dict");
+        let err = Dictionary::parse_files(path, &with_words("cab\n"), Some("bad\nfab\n")).unwrap_err();
+        assert_eq!(err.path, Path::new("words.allowed"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid dictionary words.allowed: line 2: 'fab' contains 'f', which is not in the alphabet"
+        );
+        let err = Dictionary::parse_files(path, &with_words("fab\n"), Some("bad\n")).unwrap_err();
+        assert_eq!(err.path, path);
+    }
+
+    #[test]
+    fn allowed_files_are_held_to_the_header() {
+        let pair_error = |allowed: &str| {
+            let err = Dictionary::parse_pair(&with_words("cab\n"), allowed).unwrap_err();
+            (err.line, err.kind)
+        };
+        assert_eq!(
+            pair_error("bad\nzzzzzzz\n"),
+            (
+                2,
+                DictionaryErrorKind::InvalidLetter {
+                    word: "zzzzzzz".to_string(),
+                    letter: 'z',
+                }
+            )
+        );
+        assert_eq!(
+            pair_error("abed\n"),
+            (
+                1,
+                DictionaryErrorKind::WrongLength {
+                    word: "abed".to_string(),
+                    expected: 3,
+                }
+            )
+        );
+        assert_eq!(pair_error("bad\ncab\n"), (2, DictionaryErrorKind::DuplicateWord("cab".to_string())));
+        // An allowed file's own header doesn't widen the alphabet.
+        assert_eq!(
+            pair_error("birdle-dict 1\nlanguage: xx\nalphabet: xyz\nxyz\n").1,
+            DictionaryErrorKind::InvalidLetter {
+                word: "xyz".to_string(),
+                letter: 'x',
+            }
+        );
+
+        let bare = Dictionary::parse_pair("cab\n", "fed\n").unwrap();
+        assert_eq!(bare.alphabet(), ['a', 'b', 'c', 'd', 'e', 'f']);
+        assert_eq!(bare.answers(), ["cab"]);
+    }
+
+    #[test]
+    fn open_reads_the_sibling_allowed_file() {
+        let dir = std::env::temp_dir().join(format!("birdle-dict-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("words.dict");
+        std::fs::write(&path, with_words("cab\n")).unwrap();
+        assert!(!Dictionary::open(&path).unwrap().is_allowed("bad"));
+
+        std::fs::write(dir.join("words.allowed"), "bad\n").unwrap();
+        assert!(Dictionary::open(&path).unwrap().is_allowed("bad"));
+
+        std::fs::write(dir.join("words.allowed"), "bad\nbad\n").unwrap();
+        let err = Dictionary::open(&path).unwrap_err();
+        assert_eq!(err.path, dir.join("words.allowed"));
+        assert!(matches!(err.kind, OpenErrorKind::Invalid(DictionaryError { line: 2, .. })));
+
+        let missing = Dictionary::open(&dir.join("missing.dict")).unwrap_err();
+        assert!(matches!(missing.kind, OpenErrorKind::Read(_)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
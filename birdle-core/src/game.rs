@@ -0,0 +1,278 @@
+use std::fmt;
+
+use crate::config::Config;
+use crate::dictionary::Dictionary;
+use crate::hard_mode::{check_hard_mode, HardModeViolation};
+use crate::score::{score_guess, LetterScore};
+
+/// Where a round stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    InProgress,
+    Won,
+    Lost,
+}
+
+/// A submitted guess together with the feedback for each of its tiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub word: String,
+    pub scores: Vec<LetterScore>,
+}
+
+/// Why a guess was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    WrongLength { expected: usize, found: usize },
+    NotInWordList,
+    HardMode(HardModeViolation),
+    Finished,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::WrongLength { expected, found } => {
+                write!(f, "expected {} letters, got {}", expected, found)
+            }
+            GuessError::NotInWordList => write!(f, "not in word list"),
+            GuessError::HardMode(violation) => write!(f, "hard mode: {}", violation),
+            GuessError::Finished => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// Everything needed to pick a round back up later.
+///
+/// Only the words are kept; [`Game::restore`] scores them again, so a saved
+/// round can't carry feedback that doesn't match its answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedGame {
+    pub config: Config,
+    pub answer: String,
+    pub guesses: Vec<String>,
+}
+
+/// A single round: one answer and the guesses made against it.
+#[derive(Debug, Clone)]
+pub struct Game {
+    config: Config,
+    answer: String,
+    guesses: Vec<Guess>,
+    status: Status,
+}
+
+impl Game {
+    /// Starts a round. `answer` must be `config.word_length` letters long.
+    pub fn new(answer: impl Into<String>, config: Config) -> Self {
+        let answer = answer.into();
+        assert!(config.fits(&answer), "Answer does not match the word length");
+        assert!(config.max_guesses > 0, "A game needs at least one guess");
+        Game {
+            config,
+            answer,
+            guesses: Vec::new(),
+            status: Status::InProgress,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Checks `guess` against `dictionary`, scores it and advances the round.
+    pub fn submit(&mut self, guess: &str, dictionary: &Dictionary) -> Result<&Guess, GuessError> {
+        if self.status != Status::InProgress {
+            return Err(GuessError::Finished);
+        }
+        if !self.config.fits(guess) {
+            return Err(GuessError::WrongLength {
+                expected: self.config.word_length,
+                found: guess.chars().count(),
+            });
+        }
+        if !dictionary.is_allowed(guess) {
+            return Err(GuessError::NotInWordList);
+        }
+        if self.config.hard_mode {
+            check_hard_mode(&self.guesses, guess).map_err(GuessError::HardMode)?;
+        }
+
+        let scores = score_guess(&self.answer, guess);
+        if guess == self.answer {
+            self.status = Status::Won;
+        } else if self.guesses.len() + 1 == self.config.max_guesses {
+            self.status = Status::Lost;
+        }
+        self.guesses.push(Guess {
+            word: guess.to_string(),
+            scores,
+        });
+        Ok(self.guesses.last().expect("Guess was just pushed"))
+    }
+
+    /// The answer and the words guessed so far.
+    pub fn save(&self) -> SavedGame {
+        SavedGame {
+            config: self.config,
+            answer: self.answer.clone(),
+            guesses: self.guesses.iter().map(|guess| guess.word.clone()).collect(),
+        }
+    }
+
+    /// Replays a saved round against `dictionary`.
+    ///
+    /// Returns `None` if the answer no longer fits the config or any of the
+    /// guesses would not be accepted, e.g. because the word list changed.
+    pub fn restore(saved: &SavedGame, dictionary: &Dictionary) -> Option<Self> {
+        if !saved.config.fits(&saved.answer) || saved.config.max_guesses == 0 {
+            return None;
+        }
+        let mut game = Game::new(saved.answer.as_str(), saved.config);
+        for guess in &saved.guesses {
+            game.submit(guess, dictionary).ok()?;
+        }
+        Some(game)
+    }
+
+    /// The best state seen so far for `letter`, if it has been guessed.
+    ///
+    /// This never goes down: once a letter has been `Correct` anywhere it
+    /// stays `Correct`, even if later guesses place it elsewhere.
+    pub fn key_score(&self, letter: char) -> Option<LetterScore> {
+        self.guesses
+            .iter()
+            .flat_map(|guess| guess.word.chars().zip(guess.scores.iter().copied()))
+            .filter(|(c, _)| *c == letter)
+            .map(|(_, score)| score)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new(
+            vec!["crane".to_string(), "theme".to_string()],
+            ["geese", "slate", "adieu"].iter().map(|word| word.to_string()),
+        )
+    }
+
+    fn config(max_guesses: usize) -> Config {
+        Config {
+            max_guesses,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn guessing_the_answer_wins() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        game.submit("slate", &dictionary).unwrap();
+        assert_eq!(game.status(), Status::InProgress);
+        let guess = game.submit("crane", &dictionary).unwrap();
+        assert!(guess.scores.iter().all(|score| *score == LetterScore::Correct));
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn missing_with_the_last_guess_loses() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(2));
+        game.submit("slate", &dictionary).unwrap();
+        assert_eq!(game.status(), Status::InProgress);
+        game.submit("adieu", &dictionary).unwrap();
+        assert_eq!(game.status(), Status::Lost);
+    }
+
+    #[test]
+    fn winning_with_the_last_guess_wins() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(1));
+        game.submit("crane", &dictionary).unwrap();
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn finished_rounds_take_no_more_guesses() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        game.submit("crane", &dictionary).unwrap();
+        assert_eq!(game.submit("slate", &dictionary), Err(GuessError::Finished));
+        assert_eq!(game.guesses().len(), 1);
+    }
+
+    #[test]
+    fn guesses_must_have_the_right_length() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        assert_eq!(
+            game.submit("cranes", &dictionary),
+            Err(GuessError::WrongLength { expected: 5, found: 6 })
+        );
+        assert!(game.guesses().is_empty());
+    }
+
+    #[test]
+    fn guesses_must_be_in_the_word_list() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        assert_eq!(game.submit("zzzzz", &dictionary), Err(GuessError::NotInWordList));
+        assert!(game.guesses().is_empty());
+    }
+
+    #[test]
+    fn keys_keep_their_best_score() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        assert_eq!(game.key_score('a'), None);
+        game.submit("adieu", &dictionary).unwrap();
+        assert_eq!(game.key_score('a'), Some(LetterScore::Present));
+        assert_eq!(game.key_score('d'), Some(LetterScore::Absent));
+        game.submit("slate", &dictionary).unwrap();
+        assert_eq!(game.key_score('a'), Some(LetterScore::Correct));
+        // Placed wrongly again, and the second E of "theme" is absent.
+        game.submit("adieu", &dictionary).unwrap();
+        game.submit("theme", &dictionary).unwrap();
+        assert_eq!(game.key_score('a'), Some(LetterScore::Correct));
+        assert_eq!(game.key_score('e'), Some(LetterScore::Correct));
+        assert_eq!(game.key_score('z'), None);
+    }
+
+    #[test]
+    fn restore_replays_a_saved_round() {
+        let dictionary = dictionary();
+        let mut game = Game::new("crane", config(6));
+        game.submit("slate", &dictionary).unwrap();
+        game.submit("theme", &dictionary).unwrap();
+        let restored = Game::restore(&game.save(), &dictionary).unwrap();
+        assert_eq!(restored.guesses(), game.guesses());
+        assert_eq!(restored.status(), Status::InProgress);
+    }
+
+    #[test]
+    fn restore_rejects_guesses_the_word_list_no_longer_has() {
+        let mut game = Game::new("crane", config(6));
+        game.submit("slate", &dictionary()).unwrap();
+        let changed = Dictionary::new(vec!["crane".to_string()], Vec::new());
+        assert!(Game::restore(&game.save(), &changed).is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use crate::game::Guess;
+use crate::score::LetterScore;
+
+/// A hint from an earlier guess that a hard-mode guess failed to reuse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HardModeViolation {
+    /// `letter` is known to be at `position` (zero based) but was moved or dropped.
+    MissingCorrect { letter: char, position: usize },
+    /// The answer is known to hold at least `count` copies of `letter`.
+    MissingPresent { letter: char, count: usize },
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HardModeViolation::MissingCorrect { letter, position } => {
+                write!(f, "letter {} must be {}", position + 1, letter.to_uppercase())
+            }
+            HardModeViolation::MissingPresent { letter, count: 1 } => {
+                write!(f, "guess must contain {}", letter.to_uppercase())
+            }
+            HardModeViolation::MissingPresent { letter, count } => {
+                write!(f, "guess must contain {} {}s", count, letter.to_uppercase())
+            }
+        }
+    }
+}
+
+/// Checks that `guess` keeps every hint revealed by `previous`.
+///
+/// Letters marked correct must stay in place, and each letter must appear at
+/// least as often as any single earlier guess proved it to be in the answer.
+pub fn check_hard_mode(previous: &[Guess], guess: &str) -> Result<(), HardModeViolation> {
+    let letters: Vec<char> = guess.chars().collect();
+
+    for earlier in previous {
+        for (position, (letter, score)) in earlier.word.chars().zip(&earlier.scores).enumerate() {
+            if *score == LetterScore::Correct && letters.get(position) != Some(&letter) {
+                return Err(HardModeViolation::MissingCorrect { letter, position });
+            }
+        }
+    }
+
+    for earlier in previous {
+        for letter in earlier.word.chars() {
+            let count = earlier
+                .word
+                .chars()
+                .zip(&earlier.scores)
+                .filter(|(c, score)| *c == letter && **score != LetterScore::Absent)
+                .count();
+            if letters.iter().filter(|c| **c == letter).count() < count {
+                return Err(HardModeViolation::MissingPresent { letter, count });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score::score_guess;
+
+    fn guesses(answer: &str, words: &[&str]) -> Vec<Guess> {
+        words
+            .iter()
+            .map(|word| Guess {
+                word: word.to_string(),
+                scores: score_guess(answer, word),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn correct_letters_must_stay_in_place() {
+        let previous = guesses("crane", &["slate"]);
+        assert_eq!(
+            check_hard_mode(&previous, "adieu"),
+            Err(HardModeViolation::MissingCorrect { letter: 'a', position: 2 })
+        );
+    }
+
+    #[test]
+    fn present_letters_must_be_reused() {
+        let previous = guesses("crane", &["adieu"]);
+        assert_eq!(
+            check_hard_mode(&previous, "stomp"),
+            Err(HardModeViolation::MissingPresent { letter: 'a', count: 1 })
+        );
+        assert_eq!(check_hard_mode(&previous, "stomp").unwrap_err().to_string(), "guess must contain A");
+    }
+
+    #[test]
+    fn repeated_letters_must_be_reused_as_often() {
+        // Both Es are misplaced, so a guess with one E drops a hint.
+        let previous = guesses("theme", &["ether"]);
+        let err = check_hard_mode(&previous, "thyme").unwrap_err();
+        assert_eq!(err, HardModeViolation::MissingPresent { letter: 'e', count: 2 });
+        assert_eq!(err.to_string(), "guess must contain 2 Es");
+        assert_eq!(check_hard_mode(&previous, "teeth"), Ok(()));
+    }
+
+    #[test]
+    fn guesses_keeping_every_hint_pass() {
+        let previous = guesses("crane", &["adieu", "slate"]);
+        assert_eq!(check_hard_mode(&previous, "crane"), Ok(()));
+        assert_eq!(check_hard_mode(&previous, "frame"), Ok(()));
+        // Letters already ruled out may be guessed again.
+        assert_eq!(check_hard_mode(&previous, "plate"), Ok(()));
+        assert_eq!(check_hard_mode(&[], "zzzzz"), Ok(()));
+    }
+}
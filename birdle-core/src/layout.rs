@@ -0,0 +1,110 @@
+/// An on-screen keyboard layout, as rows of letters from top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub name: &'static str,
+    /// Letters of each row. A layout without rows lays the alphabet out in
+    /// its own order.
+    pub rows: &'static [&'static str],
+}
+
+pub const QWERTY: Layout = Layout {
+    name: "qwerty",
+    rows: &["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+};
+
+pub const AZERTY: Layout = Layout {
+    name: "azerty",
+    rows: &["azertyuiop", "qsdfghjklm", "wxcvbn"],
+};
+
+pub const QWERTZ: Layout = Layout {
+    name: "qwertz",
+    rows: &["qwertzuiopü", "asdfghjklöä", "yxcvbnm"],
+};
+
+pub const DVORAK: Layout = Layout {
+    name: "dvorak",
+    rows: &["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"],
+};
+
+pub const ABC: Layout = Layout {
+    name: "abc",
+    rows: &[],
+};
+
+/// Every built-in layout, in the order settings cycle through them.
+pub const LAYOUTS: [Layout; 5] = [QWERTY, AZERTY, QWERTZ, DVORAK, ABC];
+
+impl Layout {
+    pub fn by_name(name: &str) -> Option<Layout> {
+        LAYOUTS.iter().copied().find(|layout| layout.name == name)
+    }
+
+    /// The rows to show for a dictionary with `alphabet`, at most
+    /// `keys_per_row` keys wide.
+    ///
+    /// Letters the alphabet lacks are left out. Letters the layout lacks go
+    /// in extra rows on top, the way a number row would sit.
+    pub fn rows_for(&self, alphabet: &[char], keys_per_row: usize) -> Vec<Vec<char>> {
+        if self.rows.is_empty() {
+            return alphabet.chunks(keys_per_row).map(|chunk| chunk.to_vec()).collect();
+        }
+        let extra: Vec<char> = alphabet
+            .iter()
+            .copied()
+            .filter(|c| !self.rows.iter().any(|row| row.contains(*c)))
+            .collect();
+        let mut rows: Vec<Vec<char>> = extra.chunks(keys_per_row).map(|chunk| chunk.to_vec()).collect();
+        rows.extend(
+            self.rows
+                .iter()
+                .map(|row| row.chars().filter(|c| alphabet.contains(c)).collect::<Vec<char>>())
+                .filter(|row| !row.is_empty()),
+        );
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alphabet(letters: &str) -> Vec<char> {
+        letters.chars().collect()
+    }
+
+    fn rows(rows: &[Vec<char>]) -> Vec<String> {
+        rows.iter().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn letters_the_alphabet_lacks_are_left_out() {
+        let english = alphabet("abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(
+            rows(&QWERTZ.rows_for(&english, 10)),
+            ["qwertzuiop", "asdfghjkl", "yxcvbnm"]
+        );
+        assert_eq!(rows(&DVORAK.rows_for(&alphabet("abc"), 10)), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn letters_the_layout_lacks_go_on_top() {
+        let spanish = alphabet("abcdefghijklmnñopqrstuvwxyz");
+        assert_eq!(
+            rows(&QWERTY.rows_for(&spanish, 10)),
+            ["ñ", "qwertyuiop", "asdfghjkl", "zxcvbnm"]
+        );
+        let polish = alphabet("aąbcćdeęfghijklłmnńoóprsśtuwyzźż");
+        assert_eq!(rows(&QWERTY.rows_for(&polish, 4))[..3], ["ąćęł", "ńóśź", "ż"]);
+    }
+
+    #[test]
+    fn abc_chunks_the_alphabet_in_order() {
+        let english = alphabet("abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(
+            rows(&ABC.rows_for(&english, 10)),
+            ["abcdefghij", "klmnopqrst", "uvwxyz"]
+        );
+        assert!(ABC.rows_for(&[], 10).is_empty());
+    }
+}
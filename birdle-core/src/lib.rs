@@ -0,0 +1,31 @@
+//! The rules of Birdle, free of any frontend.
+//!
+//! A [`Game`] holds the answer and the guesses made so far, scores each
+//! submitted guess and tracks whether the round has been won or lost. The
+//! Bevy app, and anything else that wants to play a round, drives it.
+
+mod code;
+mod config;
+pub mod daily;
+mod dictionary;
+mod game;
+mod hard_mode;
+pub mod layout;
+mod pick;
+mod rng;
+mod score;
+mod share;
+pub mod solver;
+mod stats;
+
+pub use code::{seeded_index, CodeError, PuzzleCode};
+pub use config::{Config, ConfigError, MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+pub use dictionary::{
+    allowed_path, Dictionary, DictionaryError, DictionaryErrorKind, OpenError, OpenErrorKind, WordInfo, FORMAT_VERSION,
+};
+pub use game::{Game, Guess, GuessError, SavedGame, Status};
+pub use hard_mode::{check_hard_mode, HardModeViolation};
+pub use pick::{pick_answer, Mode, Pick, PickError};
+pub use score::{score_guess, LetterScore};
+pub use share::share_text;
+pub use stats::Stats;
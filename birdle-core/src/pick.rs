@@ -0,0 +1,159 @@
+use std::fmt;
+
+use crate::code::{seeded_index, PuzzleCode};
+use crate::config::Config;
+use crate::daily::{self, Date};
+use crate::dictionary::Dictionary;
+
+/// Where a round's answer comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    #[default]
+    Random,
+    /// The same answer for everyone on the same day.
+    Daily,
+    Seeded(u64),
+    /// A puzzle someone else shared.
+    Code(PuzzleCode),
+}
+
+/// The answer picked for a round, and what it can be shared as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pick {
+    pub answer: String,
+    /// The config to play it with. A code sets its own word length.
+    pub config: Config,
+    /// Names the answer for anyone with the same word list.
+    pub code: PuzzleCode,
+    /// The daily puzzle number, in daily mode.
+    pub puzzle: Option<u32>,
+}
+
+/// Why no answer could be picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickError {
+    /// The dictionary has no answers of the configured length.
+    NoAnswers(usize),
+    /// The date is before the first daily puzzle.
+    NoDailyPuzzle,
+    /// The code is for a word length or answer this dictionary doesn't have,
+    /// usually because it came from a different word list.
+    UnknownCode(PuzzleCode),
+}
+
+impl fmt::Display for PickError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PickError::NoAnswers(length) => write!(f, "no {}-letter puzzle in this dictionary", length),
+            PickError::NoDailyPuzzle => write!(f, "there is no daily puzzle for today"),
+            PickError::UnknownCode(code) => write!(f, "unknown puzzle code {}", code),
+        }
+    }
+}
+
+impl std::error::Error for PickError {}
+
+/// Picks the answer for `mode` from the answers that fit `config`.
+///
+/// `date` is only used in daily mode and `random_seed` only in random mode,
+/// so every frontend deals the same answer for the same daily, seed or code.
+pub fn pick_answer(
+    dictionary: &Dictionary,
+    config: Config,
+    mode: Mode,
+    date: Date,
+    random_seed: u64,
+) -> Result<Pick, PickError> {
+    let mut config = config;
+    if let Mode::Code(code) = mode {
+        if !Config::is_playable_length(code.word_length) {
+            return Err(PickError::UnknownCode(code));
+        }
+        config.word_length = code.word_length;
+    }
+    let answers: Vec<&String> = dictionary.answers().iter().filter(|word| config.fits(word)).collect();
+    let mut puzzle = None;
+    let index = match mode {
+        Mode::Random => seeded_index(random_seed, answers.len()),
+        Mode::Daily => {
+            let number = daily::puzzle_number(date).ok_or(PickError::NoDailyPuzzle)?;
+            puzzle = Some(number);
+            daily::daily_index(answers.len(), number)
+        }
+        Mode::Seeded(seed) => seeded_index(seed, answers.len()),
+        Mode::Code(code) if code.index < answers.len() => Some(code.index),
+        Mode::Code(code) => return Err(PickError::UnknownCode(code)),
+    };
+    let index = index.ok_or(PickError::NoAnswers(config.word_length))?;
+    Ok(Pick {
+        answer: answers[index].clone(),
+        config,
+        code: PuzzleCode::new(config.word_length, index),
+        puzzle,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Date = Date {
+        year: 2022,
+        month: 3,
+        day: 1,
+    };
+
+    fn dictionary() -> Dictionary {
+        let words = ["crane", "slate", "theme", "adieu", "bird", "word"];
+        Dictionary::new(words.iter().map(|word| word.to_string()).collect(), [])
+    }
+
+    fn pick(mode: Mode) -> Result<Pick, PickError> {
+        pick_answer(&dictionary(), Config::default(), mode, DAY, 7)
+    }
+
+    #[test]
+    fn seeds_and_days_pick_the_same_answer_every_time() {
+        let daily = pick(Mode::Daily).unwrap();
+        assert_eq!(daily.puzzle, Some(59));
+        assert_eq!(daily.answer, "theme");
+        assert_eq!(pick(Mode::Daily), Ok(daily));
+        assert_eq!(pick(Mode::Seeded(7)), pick(Mode::Random));
+        assert_eq!(pick(Mode::Random).unwrap().puzzle, None);
+    }
+
+    #[test]
+    fn codes_name_the_answer_they_were_shared_for() {
+        let seeded = pick(Mode::Seeded(42)).unwrap();
+        assert_eq!(pick(Mode::Code(seeded.code)), Ok(seeded));
+
+        let short = pick(Mode::Code(PuzzleCode::new(4, 1))).unwrap();
+        assert_eq!(short.answer, "word");
+        assert_eq!(short.config.word_length, 4);
+    }
+
+    #[test]
+    fn codes_from_other_word_lists_are_rejected() {
+        for code in [PuzzleCode::new(5, 4), PuzzleCode::new(3, 0), PuzzleCode::new(12, 0)] {
+            assert_eq!(pick(Mode::Code(code)), Err(PickError::UnknownCode(code)));
+        }
+    }
+
+    #[test]
+    fn lengths_without_answers_are_reported() {
+        let config = Config {
+            word_length: 6,
+            ..Config::default()
+        };
+        assert_eq!(
+            pick_answer(&dictionary(), config, Mode::Random, DAY, 7),
+            Err(PickError::NoAnswers(6))
+        );
+        let before = Date::new(2021, 12, 31);
+        assert_eq!(
+            pick_answer(&dictionary(), Config::default(), Mode::Daily, before, 7),
+            Err(PickError::NoDailyPuzzle)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+/// How a single letter of a guess relates to the answer.
+///
+/// Variants are ordered from least to most informative, so the best thing
+/// known about a letter is the maximum over every place it was guessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LetterScore {
+    /// The letter is not in the answer.
+    Absent,
+    /// The letter is in the answer, but somewhere else.
+    Present,
+    /// The letter is in the answer at this position.
+    Correct,
+}
+
+/// Scores every letter of `guess` against `answer`.
+///
+/// Exact matches are settled first. Each remaining letter is `Present` only
+/// while the answer still has an unmatched copy of it, so a guess never shows
+/// more copies of a letter than the answer contains.
+pub fn score_guess(answer: &str, guess: &str) -> Vec<LetterScore> {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    let mut scores = vec![LetterScore::Absent; guess.len()];
+    let mut unmatched = Vec::with_capacity(answer.len());
+
+    for (i, &a) in answer.iter().enumerate() {
+        if guess.get(i) == Some(&a) {
+            scores[i] = LetterScore::Correct;
+        } else {
+            unmatched.push(a);
+        }
+    }
+
+    for (i, letter) in guess.iter().enumerate() {
+        if scores[i] == LetterScore::Correct {
+            continue;
+        }
+        if let Some(pos) = unmatched.iter().position(|a| a == letter) {
+            unmatched.swap_remove(pos);
+            scores[i] = LetterScore::Present;
+        }
+    }
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterScore::{Absent as A, Correct as C, Present as P};
+
+    #[test]
+    fn scores_each_letter() {
+        assert_eq!(score_guess("crane", "crane"), [C, C, C, C, C]);
+        assert_eq!(score_guess("crane", "nacre"), [P, P, P, P, C]);
+        assert_eq!(score_guess("crane", "tilts"), [A, A, A, A, A]);
+    }
+
+    #[test]
+    fn exact_matches_use_up_duplicates_first() {
+        // Both of theme's e's are matched in place, so geese's first e gets nothing.
+        assert_eq!(score_guess("theme", "geese"), [A, A, C, A, C]);
+        assert_eq!(score_guess("abbey", "bbbbb"), [A, C, C, A, A]);
+    }
+
+    #[test]
+    fn duplicates_are_present_only_while_copies_remain() {
+        assert_eq!(score_guess("abbey", "babes"), [P, P, C, C, A]);
+        assert_eq!(score_guess("babes", "abbey"), [P, P, C, C, A]);
+        assert_eq!(score_guess("crane", "eerie"), [A, A, P, A, C]);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::game::{Game, Status};
+use crate::score::LetterScore;
+
+/// A spoiler-free summary of a round, for pasting elsewhere.
+///
+/// The first line reads like `Birdle #42 4/6*`: `label` names the puzzle,
+/// then comes the number of guesses taken (`X` for a loss) and a `*` for hard
+/// mode. Each guess follows as a row of coloured squares; letters are never
+/// included.
+pub fn share_text(label: &str, game: &Game) -> String {
+    let taken = match game.status() {
+        Status::Won => game.guesses().len().to_string(),
+        Status::Lost => "X".to_string(),
+        Status::InProgress => "-".to_string(),
+    };
+    let mut text = format!("Birdle {} {}/{}", label, taken, game.config().max_guesses);
+    if game.config().hard_mode {
+        text.push('*');
+    }
+    text.push('\n');
+    for guess in game.guesses() {
+        text.push('\n');
+        text.extend(guess.scores.iter().copied().map(square));
+    }
+    text
+}
+
+fn square(score: LetterScore) -> char {
+    match score {
+        LetterScore::Absent => '⬛',
+        LetterScore::Present => '🟨',
+        LetterScore::Correct => '🟩',
+    }
+}
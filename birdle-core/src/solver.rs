@@ -0,0 +1,300 @@
+use std::cmp::Ordering;
+
+use crate::config::Config;
+use crate::dictionary::Dictionary;
+use crate::game::{Game, Guess};
+use crate::hard_mode::check_hard_mode;
+use crate::score::{score_guess, LetterScore};
+
+/// A word worth guessing next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// Expected information from the feedback, in bits. Each bit halves the
+    /// remaining candidates on average.
+    pub entropy: f64,
+    /// Whether the word could still be the answer.
+    pub candidate: bool,
+}
+
+/// How one guess of a finished round compares with the best one available.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Review {
+    pub word: String,
+    pub entropy: f64,
+    /// The highest ranked guess at that point, if any word could be guessed.
+    pub best: Option<Suggestion>,
+    /// Candidates before and after the guess was made.
+    pub remaining_before: usize,
+    pub remaining_after: usize,
+}
+
+/// Narrows down the answers a round could have, from the feedback given so far.
+#[derive(Debug, Clone)]
+pub struct Solver<'a> {
+    dictionary: &'a Dictionary,
+    config: Config,
+    guesses: Vec<Guess>,
+    candidates: Vec<&'a str>,
+    /// The letters of each candidate, so ranking doesn't split them up again
+    /// for every guess it tries.
+    letters: Vec<Vec<char>>,
+}
+
+impl<'a> Solver<'a> {
+    /// A solver for a round that hasn't had any guesses yet.
+    pub fn new(dictionary: &'a Dictionary, config: Config) -> Self {
+        let candidates: Vec<&str> = dictionary
+            .answers()
+            .iter()
+            .filter(|word| config.fits(word))
+            .map(String::as_str)
+            .collect();
+        Solver {
+            dictionary,
+            config,
+            guesses: Vec::new(),
+            letters: candidates.iter().map(|word| word.chars().collect()).collect(),
+            candidates,
+        }
+    }
+
+    /// A solver that has seen every guess made in `game`.
+    pub fn for_game(dictionary: &'a Dictionary, game: &Game) -> Self {
+        let mut solver = Solver::new(dictionary, *game.config());
+        for guess in game.guesses() {
+            solver.apply(guess);
+        }
+        solver
+    }
+
+    /// Drops every candidate that would have scored `guess` differently.
+    pub fn apply(&mut self, guess: &Guess) {
+        self.candidates
+            .retain(|candidate| score_guess(candidate, &guess.word) == guess.scores);
+        self.letters = self.candidates.iter().map(|word| word.chars().collect()).collect();
+        self.guesses.push(guess.clone());
+    }
+
+    /// The answers still consistent with every guess applied so far.
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    /// Expected information, in bits, from the feedback `guess` would get.
+    pub fn entropy(&self, guess: &str) -> f64 {
+        letter_entropy(&guess.chars().collect::<Vec<char>>(), &self.letters)
+    }
+
+    /// Up to `count` guesses, best first.
+    ///
+    /// Guesses are ranked by entropy. Ties go to words that could still be the
+    /// answer, since those might also win outright. In hard mode only guesses
+    /// that keep every revealed hint are considered.
+    pub fn suggest(&self, count: usize) -> Vec<Suggestion> {
+        if self.candidates.len() <= 2 {
+            // Nothing beats guessing one of the last two answers.
+            return self
+                .candidates
+                .iter()
+                .take(count)
+                .map(|word| self.suggestion(word))
+                .collect();
+        }
+        let mut suggestions: Vec<Suggestion> = self
+            .dictionary
+            .allowed()
+            .filter(|word| self.config.fits(word))
+            .filter(|word| !self.config.hard_mode || check_hard_mode(&self.guesses, word).is_ok())
+            .map(|word| self.suggestion(word))
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.entropy
+                .partial_cmp(&a.entropy)
+                .unwrap_or(Ordering::Equal)
+                .then(b.candidate.cmp(&a.candidate))
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        suggestions.truncate(count);
+        suggestions
+    }
+
+    fn suggestion(&self, word: &str) -> Suggestion {
+        Suggestion {
+            word: word.to_string(),
+            entropy: self.entropy(word),
+            candidate: self.candidates.contains(&word),
+        }
+    }
+}
+
+/// Goes through a round guess by guess, to see how each one measured up.
+pub fn review(dictionary: &Dictionary, game: &Game) -> Vec<Review> {
+    let mut solver = Solver::new(dictionary, *game.config());
+    let mut reviews = Vec::with_capacity(game.guesses().len());
+    for guess in game.guesses() {
+        let remaining_before = solver.candidates().len();
+        let entropy = solver.entropy(&guess.word);
+        let best = solver.suggest(1).pop();
+        solver.apply(guess);
+        reviews.push(Review {
+            word: guess.word.clone(),
+            entropy,
+            best,
+            remaining_before,
+            remaining_after: solver.candidates().len(),
+        });
+    }
+    reviews
+}
+
+/// Expected information, in bits, from guessing `guess` when the answer is
+/// equally likely to be any of `candidates`.
+pub fn entropy(guess: &str, candidates: &[&str]) -> f64 {
+    let letters: Vec<Vec<char>> = candidates.iter().map(|word| word.chars().collect()).collect();
+    letter_entropy(&guess.chars().collect::<Vec<char>>(), &letters)
+}
+
+fn letter_entropy(guess: &[char], candidates: &[Vec<char>]) -> f64 {
+    if candidates.is_empty() {
+        return 0.;
+    }
+    let mut buckets = vec![0u32; 3usize.pow(guess.len() as u32)];
+    let mut unmatched = Vec::with_capacity(guess.len());
+    for candidate in candidates {
+        buckets[pattern(candidate, guess, &mut unmatched)] += 1;
+    }
+    let total = candidates.len() as f64;
+    buckets
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// What `score_guess` would say, as a base 3 number with a digit per tile.
+///
+/// Ranking guesses scores every candidate against every allowed word, so this
+/// works on letters already split up and reuses `unmatched` between calls.
+fn pattern(answer: &[char], guess: &[char], unmatched: &mut Vec<char>) -> usize {
+    unmatched.clear();
+    for (i, &a) in answer.iter().enumerate() {
+        if guess.get(i) != Some(&a) {
+            unmatched.push(a);
+        }
+    }
+    let mut pattern = 0;
+    for (i, &letter) in guess.iter().enumerate() {
+        let score = if answer.get(i) == Some(&letter) {
+            LetterScore::Correct
+        } else if let Some(pos) = unmatched.iter().position(|&a| a == letter) {
+            unmatched.swap_remove(pos);
+            LetterScore::Present
+        } else {
+            LetterScore::Absent
+        };
+        pattern = pattern * 3 + score as usize;
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        // "fumty" is made up: it tells every answer apart, but can't be one.
+        let answers = ["baker", "faker", "maker", "taker"];
+        Dictionary::new(answers.iter().map(|word| word.to_string()).collect(), ["fumty".to_string()])
+    }
+
+    fn config(hard_mode: bool) -> Config {
+        Config {
+            hard_mode,
+            ..Config::default()
+        }
+    }
+
+    fn game(answer: &str, guesses: &[&str], hard_mode: bool) -> Game {
+        let dictionary = dictionary();
+        let mut game = Game::new(answer, config(hard_mode));
+        for guess in guesses {
+            game.submit(guess, &dictionary).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn pattern_agrees_with_score_guess() {
+        let words = ["geese", "theme", "eerie", "speed", "abbey", "babes", "llama", "allay", "sassy", "crane"];
+        let mut unmatched = Vec::new();
+        for answer in words {
+            for guess in words {
+                let expected = score_guess(answer, guess)
+                    .into_iter()
+                    .fold(0, |pattern, score| pattern * 3 + score as usize);
+                let letters = |word: &str| word.chars().collect::<Vec<char>>();
+                assert_eq!(
+                    pattern(&letters(answer), &letters(guess), &mut unmatched),
+                    expected,
+                    "{} against {}",
+                    guess,
+                    answer
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn candidates_follow_the_feedback() {
+        let dictionary = dictionary();
+        let solver = Solver::for_game(&dictionary, &game("maker", &["baker"], false));
+        assert_eq!(solver.candidates(), ["faker", "maker", "taker"]);
+        let solver = Solver::for_game(&dictionary, &game("maker", &["fumty"], false));
+        assert_eq!(solver.candidates(), ["maker"]);
+    }
+
+    #[test]
+    fn guesses_that_split_the_candidates_rank_first() {
+        let dictionary = dictionary();
+        let suggestions = Solver::new(&dictionary, config(false)).suggest(2);
+        assert_eq!(suggestions[0].word, "fumty");
+        assert!((suggestions[0].entropy - 2.).abs() < 1e-9);
+        assert!(!suggestions[0].candidate);
+        // Every answer splits the rest the same way, so the tie goes alphabetically.
+        assert_eq!(suggestions[1].word, "baker");
+        assert!(suggestions[1].candidate);
+    }
+
+    #[test]
+    fn hard_mode_only_suggests_guesses_that_keep_the_hints() {
+        let dictionary = dictionary();
+        let easy = Solver::for_game(&dictionary, &game("maker", &["baker"], false)).suggest(1);
+        assert_eq!(easy[0].word, "fumty");
+        let hard = Solver::for_game(&dictionary, &game("maker", &["baker"], true)).suggest(10);
+        assert!(hard.iter().all(|suggestion| suggestion.word.ends_with("aker")));
+        assert_eq!(hard[0].word, "faker");
+    }
+
+    #[test]
+    fn the_last_two_candidates_are_suggested_as_they_are() {
+        let dictionary = Dictionary::new(vec!["faker".to_string(), "maker".to_string()], ["fumty".to_string()]);
+        let suggestions = Solver::new(&dictionary, config(false)).suggest(5);
+        let words: Vec<&str> = suggestions.iter().map(|suggestion| suggestion.word.as_str()).collect();
+        assert_eq!(words, ["faker", "maker"]);
+        assert!(suggestions.iter().all(|suggestion| suggestion.candidate));
+    }
+
+    #[test]
+    fn review_compares_each_guess_with_the_best_one() {
+        let reviews = review(&dictionary(), &game("maker", &["baker", "maker"], false));
+        assert_eq!(reviews.len(), 2);
+        assert_eq!((reviews[0].remaining_before, reviews[0].remaining_after), (4, 3));
+        assert_eq!(reviews[0].best.as_ref().unwrap().word, "fumty");
+        assert!(reviews[0].entropy < reviews[0].best.as_ref().unwrap().entropy);
+        assert_eq!((reviews[1].remaining_before, reviews[1].remaining_after), (3, 1));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::game::{Game, Status};
+
+/// A player's record across every finished round.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct Stats {
+    pub played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// `distribution[n]` counts the wins that took `n + 1` guesses.
+    pub distribution: Vec<u32>,
+}
+
+impl Stats {
+    /// Adds a finished round. Rounds still in progress are ignored.
+    pub fn record(&mut self, game: &Game) {
+        match game.status() {
+            Status::Won => self.record_win(game.guesses().len()),
+            Status::Lost => self.record_loss(),
+            Status::InProgress => {}
+        }
+    }
+
+    pub fn record_win(&mut self, guesses: usize) {
+        self.played += 1;
+        self.wins += 1;
+        self.current_streak += 1;
+        self.max_streak = self.max_streak.max(self.current_streak);
+        if guesses > 0 {
+            if self.distribution.len() < guesses {
+                self.distribution.resize(guesses, 0);
+            }
+            self.distribution[guesses - 1] += 1;
+        }
+    }
+
+    pub fn record_loss(&mut self) {
+        self.played += 1;
+        self.current_streak = 0;
+    }
+
+    /// Share of rounds won, rounded to a whole percent.
+    pub fn win_percentage(&self) -> u32 {
+        (self.wins * 100 + self.played / 2)
+            .checked_div(self.played)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wins_grow_the_distribution_to_fit() {
+        let mut stats = Stats::default();
+        stats.record_win(3);
+        assert_eq!(stats.distribution, [0, 0, 1]);
+        stats.record_win(1);
+        stats.record_win(3);
+        assert_eq!(stats.distribution, [1, 0, 2]);
+        stats.record_win(6);
+        assert_eq!(stats.distribution, [1, 0, 2, 0, 0, 1]);
+        assert_eq!((stats.played, stats.wins), (4, 4));
+    }
+
+    #[test]
+    fn losses_end_the_streak_but_not_the_best_one() {
+        let mut stats = Stats::default();
+        stats.record_win(2);
+        stats.record_win(4);
+        stats.record_loss();
+        assert_eq!((stats.current_streak, stats.max_streak), (0, 2));
+        stats.record_win(5);
+        assert_eq!((stats.current_streak, stats.max_streak), (1, 2));
+        assert_eq!((stats.played, stats.wins), (4, 3));
+    }
+
+    #[test]
+    fn win_percentage_rounds_to_the_nearest_percent() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.win_percentage(), 0);
+        stats.record_win(1);
+        stats.record_loss();
+        stats.record_loss();
+        // 1 in 3 is 33.3%.
+        assert_eq!(stats.win_percentage(), 33);
+        stats.record_win(1);
+        assert_eq!(stats.win_percentage(), 50);
+        stats.record_win(1);
+        stats.record_win(1);
+        // 4 in 6 is 66.7%.
+        assert_eq!(stats.win_percentage(), 67);
+    }
+
+    #[test]
+    fn only_finished_rounds_are_recorded() {
+        let dictionary = crate::Dictionary::new(vec!["crane".to_string()], ["slate".to_string()]);
+        let mut game = Game::new("crane", crate::Config::default());
+        let mut stats = Stats::default();
+        game.submit("slate", &dictionary).unwrap();
+        stats.record(&game);
+        assert_eq!(stats, Stats::default());
+        game.submit("crane", &dictionary).unwrap();
+        stats.record(&game);
+        assert_eq!((stats.played, stats.distribution.as_slice()), (1, [0, 1].as_slice()));
+    }
+}
@@ -0,0 +1,222 @@
+//! Plays Birdle in a terminal, for when there's no window to open.
+//!
+//! It reads the same dictionary files as the Bevy game and plays by the same
+//! rules. Guesses are typed one per line; `quit` or the end of input gives up.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process;
+
+use birdle_core::daily::Date;
+use birdle_core::layout::{self, Layout};
+use birdle_core::{pick_answer, share_text, Config, Dictionary, Game, LetterScore, Mode, Status};
+use chrono::Datelike;
+
+const USAGE: &str = "\
+usage: birdle-tui [options]
+
+options:
+  --dict PATH      dictionary to play with (default: assets/words.dict)
+  --length N       letters per word, 4 to 8 (default: 5)
+  --guesses N      guesses per round (default: 6)
+  --hard           every guess must use the hints revealed so far
+  --layout NAME    keyboard shown under the grid: qwerty, azerty, qwertz,
+                   dvorak or abc (default: qwerty)
+  --daily          play today's daily puzzle
+  --seed N         play the puzzle for seed N
+  --code CODE      play a shared puzzle code
+
+Set NO_COLOR to play without colours.";
+
+const KEYS_PER_ROW: usize = 10;
+
+struct Options {
+    dict: PathBuf,
+    config: Config,
+    layout: Layout,
+    mode: Mode,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    let dictionary = match Dictionary::open(&options.dict) {
+        Ok(dictionary) => dictionary,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    let (game, label) = match start(&options, &dictionary) {
+        Ok(started) => started,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    let color = std::env::var_os("NO_COLOR").is_none();
+    play(game, &label, &dictionary, options.layout, color);
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        dict: PathBuf::from("assets/words.dict"),
+        config: Config::default(),
+        layout: layout::QWERTY,
+        mode: Mode::Random,
+    };
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--dict" => options.dict = PathBuf::from(value("--dict")?),
+            "--length" => {
+                options.config.word_length =
+                    Config::parse_word_length(&value("--length")?).map_err(|err| err.to_string())?
+            }
+            "--guesses" => {
+                options.config.max_guesses =
+                    Config::parse_max_guesses(&value("--guesses")?).map_err(|err| err.to_string())?
+            }
+            "--hard" => options.config.hard_mode = true,
+            "--layout" => {
+                let name = value("--layout")?;
+                options.layout = Layout::by_name(&name).ok_or_else(|| format!("unknown layout '{}'", name))?;
+            }
+            "--daily" => options.mode = Mode::Daily,
+            "--seed" => {
+                let seed = value("--seed")?;
+                options.mode = Mode::Seeded(seed.parse().map_err(|_| format!("invalid seed '{}'", seed))?);
+            }
+            "--code" => {
+                let code = value("--code")?;
+                options.mode = Mode::Code(code.parse().map_err(|err| format!("invalid code '{}': {}", code, err))?);
+            }
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("unknown option '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Picks the answer for `options.mode`, and the name the puzzle is shared under.
+fn start(options: &Options, dictionary: &Dictionary) -> Result<(Game, String), String> {
+    let now = chrono::Local::now();
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    let date = Date::new(now.year(), now.month(), now.day());
+    let pick = pick_answer(dictionary, options.config, options.mode, date, seed).map_err(|err| err.to_string())?;
+    let label = match (options.mode, pick.puzzle) {
+        (_, Some(number)) => format!("#{}", number),
+        (Mode::Seeded(seed), _) => format!("seed {}", seed),
+        _ => format!("code {}", pick.code),
+    };
+    Ok((Game::new(pick.answer, pick.config), label))
+}
+
+fn play(mut game: Game, label: &str, dictionary: &Dictionary, layout: Layout, color: bool) {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut stdout = io::stdout();
+    print_board(&game, dictionary, layout, color);
+    while game.status() == Status::InProgress {
+        print!("guess {}/{}> ", game.guesses().len() + 1, game.config().max_guesses);
+        let _ = stdout.flush();
+        let line = match lines.next() {
+            Some(Ok(line)) => line.trim().to_lowercase(),
+            _ => {
+                println!();
+                break;
+            }
+        };
+        if line == "quit" {
+            break;
+        }
+        match game.submit(&line, dictionary) {
+            Ok(_) => print_board(&game, dictionary, layout, color),
+            Err(err) => println!("{}", err),
+        }
+    }
+
+    match game.status() {
+        Status::Won => println!("Got it in {}!", game.guesses().len()),
+        Status::Lost | Status::InProgress => println!("The word was: {}", game.answer()),
+    }
+    if game.status() != Status::InProgress {
+        println!("\n{}", share_text(label, &game));
+    }
+}
+
+fn print_board(game: &Game, dictionary: &Dictionary, layout: Layout, color: bool) {
+    println!();
+    for row in 0..game.config().max_guesses {
+        let mut line = String::from("  ");
+        match game.guesses().get(row) {
+            Some(guess) => {
+                for (letter, score) in guess.word.chars().zip(guess.scores.iter().copied()) {
+                    line.push_str(&tile(letter, Some(score), color));
+                }
+            }
+            None => {
+                for _ in 0..game.config().word_length {
+                    line.push_str(&tile('·', None, color));
+                }
+            }
+        }
+        println!("{}", line);
+    }
+    println!();
+    for (i, row) in layout.rows_for(dictionary.alphabet(), KEYS_PER_ROW).iter().enumerate() {
+        let mut line = " ".repeat(i + 2);
+        for &letter in row {
+            line.push_str(&key(letter, game.key_score(letter), color));
+        }
+        println!("{}", line);
+    }
+    println!();
+}
+
+/// A grid tile. Without colours, the brackets carry the score instead:
+/// `[a]` correct, `(a)` present, ` a ` absent.
+fn tile(letter: char, score: Option<LetterScore>, color: bool) -> String {
+    let letter = letter.to_uppercase().collect::<String>();
+    match (score, color) {
+        (Some(score), true) => format!("\x1b[1;{}m {} \x1b[0m ", background(score), letter),
+        (None, true) => format!(" {}  ", letter),
+        (Some(LetterScore::Correct), false) => format!("[{}] ", letter),
+        (Some(LetterScore::Present), false) => format!("({}) ", letter),
+        (_, false) => format!(" {}  ", letter),
+    }
+}
+
+/// A letter on the keyboard summary. Without colours, keys are marked like
+/// tiles: `[a]` correct, `(a)` present, and absent letters are blanked out.
+fn key(letter: char, score: Option<LetterScore>, color: bool) -> String {
+    match (score, color) {
+        (Some(LetterScore::Absent), true) => format!("\x1b[2m{}\x1b[0m ", letter),
+        (Some(score), true) => format!("\x1b[1;{}m{}\x1b[0m ", background(score), letter),
+        (None, true) => format!("{} ", letter),
+        (Some(LetterScore::Absent), false) => "    ".to_string(),
+        (Some(LetterScore::Correct), false) => format!("[{}] ", letter),
+        (Some(LetterScore::Present), false) => format!("({}) ", letter),
+        (None, false) => format!(" {}  ", letter),
+    }
+}
+
+/// ANSI colour codes matching the game's tile colours, with dark text.
+fn background(score: LetterScore) -> &'static str {
+    match score {
+        LetterScore::Absent => "97;100",
+        LetterScore::Present => "30;43",
+        LetterScore::Correct => "30;42",
+    }
+}
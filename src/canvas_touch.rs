@@ -0,0 +1,98 @@
+//! Touches on the web canvas.
+//!
+//! winit 0.26 never reports touches in the browser, so Bevy's `Touches` stays
+//! empty there. The canvas's own pointer events are passed on to Bevy as
+//! `TouchInput`s instead, the way winit reports touches everywhere else.
+//! Native builds get touches from winit and need none of this.
+
+#[cfg(target_arch = "wasm32")]
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(target_arch = "wasm32")]
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Feeds touches on the web canvas into Bevy's input.
+pub struct CanvasTouchPlugin;
+
+impl Plugin for CanvasTouchPlugin {
+    #[cfg(target_arch = "wasm32")]
+    fn build(&self, app: &mut App) {
+        app.init_non_send_resource::<CanvasTouches>()
+            .add_system_to_stage(CoreStage::First, forward_canvas_touches);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build(&self, _app: &mut App) {}
+}
+
+/// Touches from the canvas, waiting to be handed to Bevy.
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+struct CanvasTouches {
+    queue: Rc<RefCell<Vec<TouchInput>>>,
+    /// Kept alive for as long as the game runs, or the browser drops them.
+    listeners: Vec<Closure<dyn FnMut(web_sys::PointerEvent)>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl CanvasTouches {
+    /// Starts listening once winit has put the canvas on the page.
+    fn listen(&mut self) {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.query_selector("canvas").ok().flatten());
+        let canvas = match canvas.and_then(|canvas| canvas.dyn_into::<web_sys::HtmlElement>().ok()) {
+            Some(canvas) => canvas,
+            None => return,
+        };
+        // Otherwise the browser takes a drag as a scroll or zoom and cancels the touch.
+        let _ = canvas.style().set_property("touch-action", "none");
+        let phases = [
+            ("pointerdown", TouchPhase::Started),
+            ("pointermove", TouchPhase::Moved),
+            ("pointerup", TouchPhase::Ended),
+            ("pointercancel", TouchPhase::Cancelled),
+        ];
+        for (name, phase) in phases {
+            let queue = self.queue.clone();
+            let target = canvas.clone();
+            let listener = Closure::wrap(Box::new(move |event: web_sys::PointerEvent| {
+                // Mouse and pen pointers already reach Bevy through winit.
+                if event.pointer_type() != "touch" {
+                    return;
+                }
+                // Positions start at the top left of the canvas, as they do from winit.
+                let rect = target.get_bounding_client_rect();
+                queue.borrow_mut().push(TouchInput {
+                    phase,
+                    position: Vec2::new(
+                        (event.client_x() as f64 - rect.left()) as f32,
+                        (event.client_y() as f64 - rect.top()) as f32,
+                    ),
+                    force: None,
+                    id: event.pointer_id() as u64,
+                });
+            }) as Box<dyn FnMut(web_sys::PointerEvent)>);
+            if canvas
+                .add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                .is_ok()
+            {
+                self.listeners.push(listener);
+            }
+        }
+    }
+}
+
+/// Sends the canvas's touches on to Bevy's input, before it reads them.
+#[cfg(target_arch = "wasm32")]
+fn forward_canvas_touches(mut canvas_touches: NonSendMut<CanvasTouches>, mut events: EventWriter<TouchInput>) {
+    if canvas_touches.listeners.is_empty() {
+        canvas_touches.listen();
+    }
+    for touch in canvas_touches.queue.borrow_mut().drain(..) {
+        events.send(touch);
+    }
+}
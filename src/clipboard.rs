@@ -0,0 +1,82 @@
+//! Copying text out of the game.
+//!
+//! Native builds use the system clipboard; the web build goes through
+//! `navigator.clipboard`, which may refuse outside a secure context. Either
+//! way, whether a copy worked is picked up with [`Clipboard::poll`], since the
+//! browser only says so once its promise settles.
+
+#[cfg(target_arch = "wasm32")]
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// The clipboard, kept as a non-send resource for as long as the game runs.
+///
+/// On X11 and Wayland, copied text is handed out by the program that copied
+/// it, so the native clipboard has to stay open or the text is lost.
+#[derive(Default)]
+pub struct Clipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    native: Option<arboard::Clipboard>,
+    #[cfg(not(target_arch = "wasm32"))]
+    outcome: Option<anyhow::Result<()>>,
+    #[cfg(target_arch = "wasm32")]
+    outcome: Rc<RefCell<Option<anyhow::Result<()>>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clipboard {
+    /// Starts copying `text`.
+    pub fn copy(&mut self, text: &str) {
+        self.outcome = Some(self.set_text(text));
+    }
+
+    fn set_text(&mut self, text: &str) -> anyhow::Result<()> {
+        if self.native.is_none() {
+            self.native = Some(arboard::Clipboard::new()?);
+        }
+        if let Some(clipboard) = &mut self.native {
+            clipboard.set_text(text.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// The outcome of the last copy, once it is known.
+    pub fn poll(&mut self) -> Option<anyhow::Result<()>> {
+        self.outcome.take()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(inline_js = "export function write_clipboard(text) {
+    return navigator.clipboard.writeText(text);
+}")]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn write_clipboard(text: &str) -> Result<js_sys::Promise, JsValue>;
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Clipboard {
+    /// Starts copying `text`.
+    pub fn copy(&mut self, text: &str) {
+        let promise = match write_clipboard(text) {
+            Ok(promise) => promise,
+            Err(err) => {
+                *self.outcome.borrow_mut() = Some(Err(anyhow::anyhow!("Clipboard is not available: {:?}", err)));
+                return;
+            }
+        };
+        let outcome = self.outcome.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let written = wasm_bindgen_futures::JsFuture::from(promise).await;
+            *outcome.borrow_mut() = Some(written.map(|_| ()).map_err(|err| anyhow::anyhow!("{:?}", err)));
+        });
+    }
+
+    /// The outcome of the last copy, once it is known.
+    pub fn poll(&mut self) -> Option<anyhow::Result<()>> {
+        self.outcome.borrow_mut().take()
+    }
+}
@@ -0,0 +1,77 @@
+//! Scales the board and keyboard to the window.
+//!
+//! Theme sizes are what the game looks like at its natural size. [`Fit::new`]
+//! works out how much room that takes stacked up or side by side, and picks
+//! whichever arrangement lets everything grow the most.
+
+use crate::theme::Sizes;
+
+/// How far the layout may shrink or grow from the theme's sizes.
+const MIN_SCALE: f32 = 0.3;
+const MAX_SCALE: f32 = 2.;
+/// Scales are rounded down to this step, so dragging a window edge doesn't
+/// rebuild the board on every frame.
+const SCALE_STEP: f32 = 0.05;
+/// Lines the settings buttons may wrap onto.
+const SETTINGS_LINES: f32 = 2.;
+
+/// Where the keyboard goes relative to the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrangement {
+    /// Keyboard under the grid, for tall windows and phones.
+    Portrait,
+    /// Keyboard beside the grid, for wide windows.
+    Landscape,
+}
+
+/// The sizes and arrangement that make the game fit the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub sizes: Sizes,
+    pub arrangement: Arrangement,
+}
+
+impl Fit {
+    /// Fits a grid of `columns` by `rows` tiles and a keyboard `keys` wide
+    /// and `key_rows` tall into a `width` by `height` window.
+    pub fn new(
+        sizes: &Sizes,
+        width: f32,
+        height: f32,
+        (columns, rows): (usize, usize),
+        (keys, key_rows): (usize, usize),
+    ) -> Fit {
+        let cell = (sizes.grid_height / rows.max(1) as f32).min(sizes.tile + 2. * sizes.gap);
+        let grid = (cell * columns as f32, cell * rows as f32);
+        // The keyboard is padded inside and has a margin around it.
+        let key_space = sizes.key + 2. * sizes.gap;
+        let keyboard = (
+            key_space * keys as f32 + 4. * sizes.padding,
+            key_space * key_rows as f32 + 4. * sizes.padding,
+        );
+        let message = sizes.font + 2. * sizes.gap;
+        // The streak box has twice the padding around it.
+        let score = sizes.heading_font + 2. * sizes.gap + 4. * sizes.padding;
+        let settings = SETTINGS_LINES * (sizes.font + 4. * sizes.gap);
+
+        let portrait = (
+            grid.0.max(keyboard.0),
+            keyboard.1 + message + grid.1 + score + settings,
+        );
+        let landscape = (
+            grid.0 + keyboard.0,
+            keyboard.1.max(message + grid.1) + score + settings,
+        );
+        let scale = |(w, h): (f32, f32)| (width / w).min(height / h);
+        let (arrangement, scale) = if scale(landscape) > scale(portrait) {
+            (Arrangement::Landscape, scale(landscape))
+        } else {
+            (Arrangement::Portrait, scale(portrait))
+        };
+        let scale = ((scale / SCALE_STEP).floor() * SCALE_STEP).clamp(MIN_SCALE, MAX_SCALE);
+        Fit {
+            sizes: sizes.scaled(scale),
+            arrangement,
+        }
+    }
+}
@@ -1,19 +1,76 @@
+mod canvas_touch;
+mod clipboard;
+mod fit;
+mod storage;
+mod theme;
+
 use bevy::{
     asset::{AssetLoader, LoadContext, LoadedAsset},
     prelude::*,
     reflect::TypeUuid,
     utils::BoxedFuture,
 };
+use chrono::Datelike;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Mutex;
 use rand::Rng;
 use bevy::input::keyboard::KeyboardInput;
+use bevy::input::touch::{Touch, Touches};
+use bevy::tasks::AsyncComputeTaskPool;
+use bevy::window::ReceivedCharacter;
+use birdle_core::daily::{self, Date};
+use birdle_core::layout::{self, Layout};
+use birdle_core::solver::Solver;
+use birdle_core::{
+    allowed_path, pick_answer, share_text, Config, Dictionary, Game, GuessError, LetterScore, Mode, PickError,
+    PuzzleCode, SavedGame, Stats, Status, MAX_WORD_LENGTH, MIN_WORD_LENGTH,
+};
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::prelude::*;
-use web_sys::Storage;
+use canvas_touch::CanvasTouchPlugin;
+use clipboard::Clipboard;
+use fit::{Arrangement, Fit};
+use storage::Store;
+use theme::{Sizes, Theme, ThemeLoader, Themed, Themes};
+
+const GUESS_OPTIONS: [usize; 4] = [4, 6, 8, 10];
+const KEYS_PER_ROW: usize = 10;
+// How long a rejected guess shakes its row for, and how long the reason stays up.
+const SHAKE_SECONDS: f32 = 0.4;
+const TOAST_SECONDS: f32 = 1.5;
+// Each tile of a submitted row takes FLIP_SECONDS to turn over, starting
+// FLIP_STAGGER after its left neighbour.
+const FLIP_SECONDS: f32 = 0.3;
+const FLIP_STAGGER: f32 = 0.2;
+// Browsers follow a tap with made-up mouse events; mouse clicks this soon
+// after a touch are taken to be those and ignored.
+const TAP_MOUSE_SECONDS: f64 = 1.;
+// Buttons shrink to this while held down.
+const PRESSED_SCALE: f32 = 0.9;
+/// Language code and dictionary file of every word pack.
+const LANGUAGE_PACKS: [(&str, &str); 2] = [
+    ("en", "words.dict"),
+    ("es", "es.dict"),
+];
+/// The solver's first guess for each language pack and word length, as
+/// `birdle-bench` finds it. An empty board is the slowest to rank, and the web
+/// build has no thread to rank it on.
+const OPENERS: [(&str, usize, &str); 10] = [
+    ("en", 4, "late"),
+    ("en", 5, "raise"),
+    ("en", 6, "senior"),
+    ("en", 7, "certain"),
+    ("en", 8, "decorate"),
+    ("es", 4, "rato"),
+    ("es", 5, "noria"),
+    ("es", 6, "cierto"),
+    ("es", 7, "termino"),
+    ("es", 8, "langosta"),
+];
 
 #[derive(Debug, TypeUuid)]
 #[uuid = "39cadc56-aa9c-4543-8640-a018b74b5052"]
 pub struct CustomAsset {
-    pub words: Vec<String>,
+    pub dictionary: Dictionary,
 }
 
 #[derive(Default)]
@@ -23,10 +80,12 @@ pub struct CustomAssetLoader;
 enum AppState {
     Init,
     Playing,
+    Revealing,
     Loading,
     CheckWin,
     Win,
-    Fail
+    Fail,
+    Stats,
 }
 
 struct WinNoticeMenu { 
@@ -34,7 +93,13 @@ struct WinNoticeMenu {
 }
 
 struct GameContainer {
-    game_container_entity: Entity
+    game_container_entity: Entity,
+    /// The sizes and arrangement it was built with.
+    fit: Fit,
+}
+
+struct StatsMenu {
+    stats_entity: Entity
 }
 
 impl AssetLoader for CustomAssetLoader {
@@ -45,11 +110,16 @@ impl AssetLoader for CustomAssetLoader {
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
         Box::pin(async move {
             let words = String::from_utf8(bytes.to_vec())?;
-            let vec: Vec<String> = words.lines().map(|word| {
-                String::from(word)
-            }).collect();
+            // `words.dict` can either hold both lists in `[answers]` and
+            // `[allowed]` sections, or be paired with a `words.allowed` file.
+            let allowed_path = allowed_path(load_context.path());
+            let allowed = match load_context.read_asset_bytes(&allowed_path).await {
+                Ok(allowed) => Some(String::from_utf8(allowed)?),
+                Err(_) => None,
+            };
+            let dictionary = Dictionary::parse_files(load_context.path(), &words, allowed.as_deref())?;
             load_context.set_default_asset(LoadedAsset::new(CustomAsset {
-                words: vec
+                dictionary
             }));
             Ok(())
         })
@@ -61,19 +131,56 @@ impl AssetLoader for CustomAssetLoader {
 }
 
 fn main() {
+    let store = Store::open();
+    let mut settings: Settings = store.load("settings").unwrap_or_default();
+    if let Mode::Seeded(_) | Mode::Code(_) = settings.mode {
+        settings.mode = Mode::Random;
+    }
+    if !LANGUAGE_PACKS.iter().any(|(language, _)| *language == settings.language) {
+        settings.language = LANGUAGE_PACKS[0].0.to_string();
+    }
+    let launch_mode = launch_mode();
+    if launch_mode != Mode::default() {
+        settings.mode = launch_mode;
+    }
+    let stats: Stats = store.load("stats").unwrap_or_default();
+    let (width, height) = window_size();
+    // A round given up on is saved as `null`, so it isn't picked up again.
+    let round = store.load::<Option<SavedRound>>("round").flatten();
+
     App::new()
         .add_startup_system(setup_camera)
         .insert_resource(WindowDescriptor {
             vsync: false, // This is needed because of an issue with wgpu amdvlk
-            width: 550.0,
-            height: 570.0,
+            width,
+            height,
             ..Default::default()
         })
     .add_plugins(DefaultPlugins)
-        .init_resource::<GameState>()
+        .add_plugin(CanvasTouchPlugin)
+        .insert_resource(GameState {
+            resume: round,
+            ..Default::default()
+        })
+        .insert_resource(settings)
+        .init_resource::<Theme>()
+        .insert_resource(stats)
+        .insert_resource(store)
+        .init_resource::<Taps>()
+        .init_non_send_resource::<Clipboard>()
+        .init_resource::<PendingSuggestion>()
+        .add_system(show_suggestion)
         .add_asset::<CustomAsset>()
         .init_asset_loader::<CustomAssetLoader>()
+        .add_asset::<Theme>()
+        .init_asset_loader::<ThemeLoader>()
+        .add_startup_system(setup_theme)
+        .add_system_to_stage(CoreStage::PreUpdate, theme::update_theme)
+        .add_system(follow_browser_window)
+        .add_system_to_stage(CoreStage::PreUpdate, handle_touches.after(bevy::ui::UiSystem::Focus))
+        .add_system(show_pressed)
         .add_state(AppState::Init)
+        .add_system(update_puzzle_info)
         .add_system_set(
             SystemSet::on_enter(AppState::Init)
             .with_system(setup)
@@ -110,6 +217,10 @@ fn main() {
             SystemSet::on_update(AppState::Fail)
             .with_system(show_win_notice)
         )
+        .add_system_set(
+            SystemSet::on_update(AppState::Fail)
+            .with_system(handle_share)
+        )
         .add_system_set(
             SystemSet::on_update(AppState::Win)
             .with_system(handle_accept_win)
@@ -130,10 +241,26 @@ fn main() {
             SystemSet::on_update(AppState::Win)
             .with_system(show_win_notice)
         )
+        .add_system_set(
+            SystemSet::on_update(AppState::Win)
+            .with_system(handle_share)
+        )
         .add_system_set(
             SystemSet::on_enter(AppState::CheckWin)
             .with_system(check_win)
         )
+        .add_system_set(
+            SystemSet::on_enter(AppState::Stats)
+            .with_system(spawn_stats_menu)
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Stats)
+            .with_system(handle_close_stats)
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::Stats)
+            .with_system(cleanup_stats_menu)
+        )
         .add_system_set(
             SystemSet::on_update(AppState::Playing)
             .with_system(handle_keyboard)
@@ -144,11 +271,41 @@ fn main() {
             .with_system(handle_button)
             .label("input")
         )
+        .add_system_set(
+            SystemSet::on_update(AppState::Playing)
+            .with_system(handle_settings)
+            .label("input")
+        )
         .add_system_set(
             SystemSet::on_update(AppState::Playing)
             .with_system(update_text)
             .before("input")
         )
+        .add_system_set(
+            SystemSet::on_update(AppState::Playing)
+            .with_system(restyle_game)
+            .before("input")
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Playing)
+            .with_system(update_message)
+            .after("input")
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Playing)
+            .with_system(rebuild_keyboard)
+            .after("input")
+        )
+        .add_system_to_stage(CoreStage::PostUpdate, paint_keys)
+        .add_system_to_stage(CoreStage::PostUpdate, paint_tiles)
+        .add_system_to_stage(CoreStage::PostUpdate, theme::apply_theme)
+        .add_system(update_setting_labels)
+        .add_system(shake_rejected_row)
+        .add_system(save_settings)
+        .add_system_set(
+            SystemSet::on_update(AppState::Revealing)
+            .with_system(reveal_row)
+        )
         .add_system_set(
             SystemSet::on_update(AppState::Loading)
             .with_system(check_keyboard)
@@ -164,66 +321,205 @@ fn main() {
 #[derive(Component, Debug)]
 struct Score { }
 
+#[derive(Component, Debug)]
+struct Message;
+
+#[derive(Component, Debug)]
+struct PuzzleInfo;
+
 
 #[derive(Component, Debug)]
 struct Cell {
     row: usize,
     column: usize,
-    guess: Option<char>
+    guess: Option<char>,
+    /// The score the tile shows, once it has been revealed.
+    score: Option<LetterScore>,
 }
 
+/// The corner of a tile that marks its score without relying on colour.
+#[derive(Component, Debug)]
+struct Marker;
+
 #[derive(Component, Debug)]
 struct Key {
     key: String
 }
 
+/// The on-screen keyboard, and the layout it was built with.
+#[derive(Component, Debug)]
+struct Keyboard {
+    layout: &'static str,
+}
+
+/// Holds the keyboard, so it can be rebuilt in place when the layout changes.
+#[derive(Component, Debug)]
+struct KeyboardSlot;
+
 #[derive(Default, Debug)]
 struct GameState {
     handle: Handle<CustomAsset>,
-    guesses: Vec<String>,
-    word: Option<String>,
-    printed: bool,
+    game: Option<Game>,
     guess: String,
     column: usize,
+    message: Option<String>,
+    puzzle: Option<u32>,
+    code: Option<PuzzleCode>,
+    /// The round saved by the last run, until it has been picked back up.
+    resume: Option<SavedRound>,
+    /// Whether the finished round is already counted in the stats.
+    recorded: bool,
+    /// The last guess that was turned down, while its row shakes.
+    rejected: Option<Rejection>,
+    /// The row being turned over after a guess was accepted.
+    reveal: Option<Reveal>,
+}
+
+/// A suggestion being worked out on the async compute pool, and the answer
+/// and row it is for, so a stale one isn't shown on a later row or round.
+#[derive(Default)]
+struct PendingSuggestion(Mutex<Option<(String, usize, Receiver<String>)>>);
+
+#[derive(Debug)]
+struct Rejection {
+    row: usize,
+    timer: Timer,
+}
+
+#[derive(Debug)]
+struct Reveal {
     row: usize,
-    wins: u32
+    timer: Timer,
+}
+
+impl GameState {
+    fn row(&self) -> usize {
+        self.game.as_ref().map_or(0, |game| game.guesses().len())
+    }
+
+    fn answer(&self) -> &str {
+        self.game.as_ref().expect("Game not started!").answer()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct Settings {
+    config: Config,
+    mode: Mode,
+    language: String,
+    layout: String,
+    /// Skips the tile flips and row shakes.
+    reduced_motion: bool,
+    theme: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            config: Config::default(),
+            mode: Mode::default(),
+            language: LANGUAGE_PACKS[0].0.to_string(),
+            layout: layout::QWERTY.name.to_string(),
+            reduced_motion: false,
+            theme: theme::THEMES[0].to_string(),
+        }
+    }
+}
+
+impl Settings {
+    fn layout(&self) -> Layout {
+        Layout::by_name(&self.layout).unwrap_or(layout::QWERTY)
+    }
+
+    fn dictionary_path(&self) -> &'static str {
+        LANGUAGE_PACKS.iter()
+            .find(|(language, _)| *language == self.language)
+            .unwrap_or(&LANGUAGE_PACKS[0])
+            .1
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+enum SettingButton {
+    WordLength,
+    MaxGuesses,
+    HardMode,
+    Mode,
+    Language,
+    Layout,
+    Motion,
+    Theme,
+    Stats,
+    Suggest,
+}
+
+impl SettingButton {
+    fn label(&self, settings: &Settings) -> String {
+        match self {
+            SettingButton::WordLength => format!("{} letters", settings.config.word_length),
+            SettingButton::MaxGuesses => format!("{} guesses", settings.config.max_guesses),
+            SettingButton::HardMode => {
+                format!("hard mode: {}", if settings.config.hard_mode { "on" } else { "off" })
+            }
+            SettingButton::Mode => match settings.mode {
+                Mode::Random => "random".to_string(),
+                Mode::Daily => "daily".to_string(),
+                Mode::Seeded(seed) => format!("seed {}", seed),
+                Mode::Code(code) => format!("code {}", code),
+            },
+            SettingButton::Language => settings.language.clone(),
+            SettingButton::Layout => settings.layout().name.to_string(),
+            SettingButton::Motion => {
+                format!("motion: {}", if settings.reduced_motion { "reduced" } else { "full" })
+            }
+            SettingButton::Theme => settings.theme.clone(),
+            SettingButton::Stats => "stats".to_string(),
+            SettingButton::Suggest => "suggest".to_string(),
+        }
+    }
+}
+
+/// The round in progress, saved after every guess.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedRound {
+    game: SavedGame,
+    language: String,
+    mode: Mode,
+    puzzle: Option<u32>,
+    code: Option<PuzzleCode>,
 }
 
 #[derive(Component, Debug)]
 struct WinNotice();
 
-fn spawn_keyboard(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
-    let keys = vec![
-        "q", 
-        "w",
-        "e",
-        "r",
-        "t", 
-        "y",
-        "u",
-        "i",
-        "o",
-        "p",
-        "a",
-        "s",
-        "d",
-        "f",
-        "g",
-        "h",
-        "j",
-        "k",
-        "l",
-        " ",
-        "←",
-        "z",
-        "x",
-        "c",
-        "v",
-        "b",
-        "n",
-        "m",
-        "⏎",
-            ];
+/// Copies the result grid of the finished round.
+#[derive(Component, Debug)]
+struct ShareButton;
+
+/// Closes the stats screen when clicked.
+#[derive(Component, Debug)]
+struct StatsNotice;
+
+/// The keys on each row of the keyboard, with backspace and enter either side
+/// of the last one.
+fn keyboard_rows(alphabet: &[char], layout: Layout) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = layout.rows_for(alphabet, KEYS_PER_ROW).iter()
+        .map(|row| row.iter().map(|c| c.to_string()).collect())
+        .collect();
+    if rows.is_empty() {
+        rows.push(Vec::new());
+    }
+    let last = rows.last_mut().expect("Keyboard has rows");
+    last.insert(0, "←".to_string());
+    last.push("⏎".to_string());
+    rows
+}
+
+fn spawn_keyboard(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, alphabet: &[char], layout: Layout, sizes: &Sizes) {
+    let rows = keyboard_rows(alphabet, layout);
+    let widest = rows.iter().map(|row| row.len()).max().unwrap_or(0).max(KEYS_PER_ROW);
+    let key_space = sizes.key + 2. * sizes.gap;
 
     parent.spawn_bundle(NodeBundle {
         style: Style {
@@ -231,60 +527,164 @@ fn spawn_keyboard(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
             position: Rect {
                 ..Default::default()
             },
-            flex_wrap: FlexWrap::WrapReverse,
-            size: Size::new(Val::Px((40.*12.) + 50.), Val::Px((40.*3.) + 50.)),
+            flex_direction: FlexDirection::ColumnReverse,
+            size: Size::new(
+                Val::Px(key_space * widest as f32 + 2. * sizes.padding),
+                Val::Px(key_space * rows.len() as f32 + 2. * sizes.padding),
+            ),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
             align_content: AlignContent::Center,
             ..Default::default()
         },
-        color: Color::rgb(0.9, 0.9, 0.9).into(),
         ..Default::default()
-    }).with_children(|parent| {
-    for key in keys {
-        parent
-            .spawn_bundle(ButtonBundle {
+    }).insert(Themed::Panel).with_children(|parent| {
+        for row in rows {
+            parent.spawn_bundle(NodeBundle {
                 style: Style {
                     position_type: PositionType::Relative,
-                    max_size: Size::new(Val::Px(40.), Val::Px(40.)),
-                    size: Size::new(Val::Px(40.), Val::Percent(40.0)),
-                    margin: Rect {
-                        top: Val::Px(5.),
-                        left: Val::Px(5.),
-                        right: Val::Px(5.),
-                        bottom: Val::Px(5.),
-                    },
+                    flex_direction: FlexDirection::Row,
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
-                    align_content: AlignContent::Center,
                     ..Default::default()
                 },
-                color: Color::rgb(0.15, 0.15, 0.15).into(),
+                color: Color::NONE.into(),
+                ..Default::default()
+            }).with_children(|parent| {
+                for key in row {
+                    spawn_key(parent, asset_server, &key, sizes);
+                }
+            });
+        }
+    }).insert(Keyboard { layout: layout.name });
+}
+
+fn spawn_key(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, key: &str, sizes: &Sizes) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Relative,
+                size: Size::new(Val::Px(sizes.key), Val::Px(sizes.key)),
+                margin: Rect::all(Val::Px(sizes.gap)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                align_content: AlignContent::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    .with_children(|parent| {
+        // text
+        parent.spawn_bundle(TextBundle {
+            style: Style {
+                margin: Rect::all(Val::Px(sizes.gap)),
                 ..Default::default()
+            },
+            text: Text::with_section(
+                      key,
+                      TextStyle {
+                          font: asset_server.load("fonts/FiraCode-Bold.ttf"),
+                          font_size: sizes.key_font,
+                          ..Default::default()
+                      },
+                      Default::default(),
+                  ),
+                  ..Default::default()
+        }).insert(Key {
+            key: key.to_string(),
+        }).insert(Themed::TileText);
+    });
+}
+
+/// Touches in progress, and when the screen was last touched.
+#[derive(Default)]
+struct Taps {
+    /// Seconds since startup.
+    last_touch: Option<f64>,
+    /// The button each finger went down on.
+    held: Vec<(u64, Entity)>,
+}
+
+/// Where a touch is in UI coordinates, which start at the bottom of the window.
+fn touch_position(touch: &Touch, windows: &Windows) -> Vec2 {
+    let position = touch.position();
+    // Bevy turns touches the right way up itself on phones. Elsewhere they
+    // start at the top, including the ones `canvas_touch` reads in browsers.
+    if cfg!(any(target_os = "android", target_os = "ios")) {
+        return position;
+    }
+    let height = windows.get_primary().map_or(0., |window| window.height());
+    Vec2::new(position.x, height - position.y)
+}
+
+/// Presses the button under a finger as soon as it goes down.
+///
+/// Bevy's UI only follows the mouse cursor, so on its own a tap clicks
+/// wherever the cursor last was when the finger lifts, and the mouse events a
+/// browser makes up after a tap click again. Both are undone here, so every
+/// tap presses exactly the button that was tapped, once.
+fn handle_touches(
+    time: Res<Time>,
+    touches: Res<Touches>,
+    mouse: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    mut taps: ResMut<Taps>,
+    mut node_query: Query<(Entity, &Node, &GlobalTransform, &mut Interaction)>,
+) {
+    let now = time.seconds_since_startup();
+    let touching = touches.iter().next().is_some()
+        || touches.iter_just_released().next().is_some()
+        || touches.iter_just_cancelled().next().is_some();
+    let recent = touching || taps.last_touch.map_or(false, |at| now - at < TAP_MOUSE_SECONDS);
+    if !touching && !(recent && mouse.just_pressed(MouseButton::Left)) {
+        return;
+    }
+    if touching {
+        taps.last_touch = Some(now);
+    }
+    for (_, _, _, mut interaction) in node_query.iter_mut() {
+        if *interaction == Interaction::Clicked {
+            *interaction = Interaction::None;
+        }
+    }
+
+    for touch in touches.iter_just_released().chain(touches.iter_just_cancelled()) {
+        taps.held.retain(|(id, _)| *id != touch.id());
+    }
+    for touch in touches.iter_just_pressed() {
+        let position = touch_position(touch, &windows);
+        // Children are drawn on top of their parents, so the nearest node is the one tapped.
+        let tapped = node_query
+            .iter_mut()
+            .filter(|(_, node, transform, _)| {
+                let offset = (position - transform.translation.truncate()).abs();
+                offset.x < node.size.x / 2. && offset.y < node.size.y / 2.
             })
-        .with_children(|parent| {
-            // text
-            parent.spawn_bundle(TextBundle {
-                style: Style {
-                    margin: Rect::all(Val::Px(5.0)),
-                    ..Default::default()
-                },
-                text: Text::with_section(
-                          key,
-                          TextStyle {
-                              font: asset_server.load("fonts/FiraCode-Bold.ttf"),
-                              font_size: 30.0,
-                              color: Color::WHITE,
-                          },
-                          Default::default(),
-                      ),
-                      ..Default::default()
-            }).insert(Key {
-                key: key.to_string(),
+            .max_by(|(_, _, a, _), (_, _, b, _)| {
+                a.translation.z.partial_cmp(&b.translation.z).unwrap_or(std::cmp::Ordering::Equal)
             });
-        });
+        if let Some((entity, _, _, mut interaction)) = tapped {
+            *interaction = Interaction::Clicked;
+            taps.held.push((touch.id(), entity));
+        }
+    }
+}
+
+/// Shrinks keys and buttons while they are held down.
+fn show_pressed(
+    taps: Res<Taps>,
+    mut button_query: Query<
+        (Entity, &Interaction, &mut Transform),
+        (With<Button>, Without<WinNotice>, Without<StatsNotice>),
+    >,
+) {
+    for (entity, interaction, mut transform) in button_query.iter_mut() {
+        let held = taps.held.iter().any(|(_, held)| *held == entity);
+        let scale = if held || *interaction == Interaction::Clicked { PRESSED_SCALE } else { 1. };
+        if transform.scale.x != scale {
+            transform.scale = Vec3::new(scale, scale, 1.);
+        }
     }
-    });
 }
 
 fn handle_button(
@@ -324,7 +724,21 @@ fn handle_button(
     }
 }
 
-fn spawn_win_notice(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn spawn_win_notice(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    state: Res<GameState>,
+    stats: Res<Stats>,
+    game_container: Res<GameContainer>,
+) {
+    let sizes = &game_container.fit.sizes;
+    let (rows, highlight) = match &state.game {
+        Some(game) => {
+            let won = game.status() == Status::Won;
+            (game.config().max_guesses, Some(game.guesses().len().saturating_sub(1)).filter(|_| won))
+        }
+        None => (0, None),
+    };
     let win_notice_entity = commands.spawn_bundle(NodeBundle {
             style: Style {
                 position_type: PositionType::Absolute,
@@ -338,28 +752,23 @@ fn spawn_win_notice(mut commands: Commands, asset_server: Res<AssetServer>) {
                 align_content: AlignContent::Center,
                 ..Default::default()
             },
-            color: Color::rgb(1.0, 1.0, 1.0).into(),
             ..Default::default()
-        }).with_children(|parent| {
+        }).insert(Themed::Background).with_children(|parent| {
             parent.spawn_bundle(ButtonBundle {
                 style: Style {
                     position_type: PositionType::Relative,
                     size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                    margin: Rect {
-                        top: Val::Px(5.),
-                        left: Val::Px(5.),
-                        right: Val::Px(5.),
-                        bottom: Val::Px(5.),
-                    },
+                    margin: Rect::all(Val::Px(sizes.gap)),
+                    flex_direction: FlexDirection::ColumnReverse,
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
                     align_content: AlignContent::Center,
                     ..Default::default()
                 },
-                color: Color::rgb(1.0, 1.0, 1.0).into(),
                 ..Default::default()
             })
             .insert(WinNotice {})
+            .insert(Themed::Background)
             .with_children(|button_parent| {
                 button_parent.spawn_bundle(TextBundle {
                     focus_policy: bevy::ui::FocusPolicy::Pass,
@@ -367,8 +776,8 @@ fn spawn_win_notice(mut commands: Commands, asset_server: Res<AssetServer>) {
                               "",
                               TextStyle {
                                   font: asset_server.load("fonts/FiraCode-Bold.ttf"),
-                                  font_size: 30.0,
-                                  color: Color::rgb(0.2, 0.2, 0.2),
+                                  font_size: sizes.heading_font,
+                                  ..Default::default()
                               },
                               Default::default(),
                           ),
@@ -377,13 +786,199 @@ fn spawn_win_notice(mut commands: Commands, asset_server: Res<AssetServer>) {
                               ..Default::default()
                           },
                           ..Default::default()
-                });
+                }).insert(Themed::Text);
+                button_parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            margin: Rect::all(Val::Px(sizes.padding)),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .insert(ShareButton)
+                    .insert(Themed::Correct)
+                    .with_children(|parent| {
+                        let font = asset_server.load("fonts/FiraCode-Bold.ttf");
+                        parent.spawn_bundle(stats_text(&font, "share".to_string(), sizes.font, sizes)).insert(Themed::TileText);
+                    });
+                spawn_stats(button_parent, &asset_server, &stats, rows, highlight, sizes);
             });
         }).id();
     commands.insert_resource(WinNoticeMenu { win_notice_entity});
 }
 
-fn spawn_grid(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+fn stats_text(font: &Handle<Font>, value: String, font_size: f32, sizes: &Sizes) -> TextBundle {
+    TextBundle {
+        focus_policy: bevy::ui::FocusPolicy::Pass,
+        style: Style {
+            margin: Rect::all(Val::Px(sizes.tight_gap)),
+            ..Default::default()
+        },
+        text: Text::with_section(
+                  value,
+                  TextStyle {
+                      font: font.clone(),
+                      font_size,
+                      ..Default::default()
+                  },
+                  Default::default(),
+              ),
+              ..Default::default()
+    }
+}
+
+/// The totals, followed by a bar per guess count showing how many wins took
+/// that many guesses. `highlight` marks the bar the round just played added to.
+fn spawn_stats(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    stats: &Stats,
+    rows: usize,
+    highlight: Option<usize>,
+    sizes: &Sizes,
+) {
+    let font = asset_server.load("fonts/FiraCode-Bold.ttf");
+    parent.spawn_bundle(NodeBundle {
+        focus_policy: bevy::ui::FocusPolicy::Pass,
+        style: Style {
+            position_type: PositionType::Relative,
+            flex_direction: FlexDirection::ColumnReverse,
+            align_items: AlignItems::Center,
+            margin: Rect::all(Val::Px(sizes.padding)),
+            ..Default::default()
+        },
+        color: Color::NONE.into(),
+        ..Default::default()
+    }).with_children(|parent| {
+        parent.spawn_bundle(NodeBundle {
+            focus_policy: bevy::ui::FocusPolicy::Pass,
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            ..Default::default()
+        }).with_children(|parent| {
+            for (value, label) in [
+                (stats.played, "played"),
+                (stats.win_percentage(), "win %"),
+                (stats.current_streak, "streak"),
+                (stats.max_streak, "best"),
+            ] {
+                parent.spawn_bundle(NodeBundle {
+                    focus_policy: bevy::ui::FocusPolicy::Pass,
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        margin: Rect::all(Val::Px(sizes.padding)),
+                        ..Default::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..Default::default()
+                }).with_children(|parent| {
+                    parent.spawn_bundle(stats_text(&font, value.to_string(), sizes.heading_font, sizes)).insert(Themed::Text);
+                    parent.spawn_bundle(stats_text(&font, label.to_string(), sizes.small_font, sizes)).insert(Themed::Text);
+                });
+            }
+        });
+
+        parent.spawn_bundle(stats_text(&font, "guess distribution".to_string(), sizes.font, sizes)).insert(Themed::Text);
+        let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+        for guesses in 0..rows.max(stats.distribution.len()) {
+            let count = stats.distribution.get(guesses).copied().unwrap_or(0);
+            parent.spawn_bundle(NodeBundle {
+                focus_policy: bevy::ui::FocusPolicy::Pass,
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    size: Size::new(Val::Px(sizes.bar_width), Val::Px(sizes.bar_height)),
+                    align_items: AlignItems::Center,
+                    margin: Rect::all(Val::Px(sizes.tight_gap)),
+                    ..Default::default()
+                },
+                color: Color::NONE.into(),
+                ..Default::default()
+            }).with_children(|parent| {
+                parent.spawn_bundle(stats_text(&font, format!("{:>2}", guesses + 1), sizes.small_font, sizes)).insert(Themed::Text);
+                // Empty bars keep a sliver of width so the count still fits.
+                let width = 8. + 80. * count as f32 / most as f32;
+                parent.spawn_bundle(NodeBundle {
+                    focus_policy: bevy::ui::FocusPolicy::Pass,
+                    style: Style {
+                        size: Size::new(Val::Percent(width), Val::Percent(100.)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::FlexEnd,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(if highlight == Some(guesses) { Themed::Correct } else { Themed::Absent })
+                .with_children(|parent| {
+                    parent.spawn_bundle(stats_text(&font, count.to_string(), sizes.small_font, sizes)).insert(Themed::TileText);
+                });
+            });
+        }
+    });
+}
+
+/// Lays the stats screen over the round in progress.
+fn spawn_stats_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_container: Res<GameContainer>,
+    settings: Res<Settings>,
+    stats: Res<Stats>,
+) {
+    let sizes = &game_container.fit.sizes;
+    let mut stats_entity = None;
+    commands.entity(game_container.game_container_entity).with_children(|parent| {
+        stats_entity = Some(parent.spawn_bundle(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.),
+                    bottom: Val::Px(0.),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(StatsNotice)
+        .insert(Themed::Background)
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/FiraCode-Bold.ttf");
+            parent.spawn_bundle(stats_text(&font, "Statistics\n(click)".to_string(), sizes.heading_font, sizes)).insert(Themed::Text);
+            spawn_stats(parent, &asset_server, &stats, settings.config.max_guesses, None, sizes);
+        }).id());
+    });
+    if let Some(stats_entity) = stats_entity {
+        commands.insert_resource(StatsMenu { stats_entity });
+    }
+}
+
+fn handle_close_stats(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatsNotice>)>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            app_state.set(AppState::Playing).expect("Failed to transition to playing");
+        }
+    }
+}
+
+fn spawn_grid(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, config: &Config, sizes: &Sizes) {
+    // Rows shrink to fit the grid height, with a margin on every side of a tile.
+    let cell_size = (sizes.grid_height / config.max_guesses as f32).min(sizes.tile + 2. * sizes.gap);
+    let tile_size = cell_size - 2. * sizes.gap;
+    let width = cell_size * config.word_length as f32;
+    let height = cell_size * config.max_guesses as f32;
     parent.spawn_bundle(NodeBundle {
         style: Style {
             position_type: PositionType::Relative,
@@ -391,15 +986,14 @@ fn spawn_grid(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
                 ..Default::default()
             },
             flex_wrap: FlexWrap::WrapReverse,
-            size: Size::new(Val::Px((40.*5.) + 50.), Val::Px((40.*6.) + 60.)),
+            size: Size::new(Val::Px(width), Val::Px(height)),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
             align_content: AlignContent::Center,
             ..Default::default()
         },
-        color: Color::rgb(0.9, 0.9, 0.9).into(),
         ..Default::default()
-    }).with_children(|parent| {
+    }).insert(Themed::Panel).with_children(|parent| {
         parent.spawn_bundle(NodeBundle {
             style: Style {
                 position_type: PositionType::Relative,
@@ -407,59 +1001,76 @@ fn spawn_grid(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
                     ..Default::default()
                 },
                 flex_wrap: FlexWrap::WrapReverse,
-                size: Size::new(Val::Px((40.*5.) + 50.), Val::Px((40.*6.) + 60.)),
+                size: Size::new(Val::Px(width), Val::Px(height)),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
                 align_content: AlignContent::Center,
                 ..Default::default()
             },
-            color: Color::rgb(0.9, 0.9, 0.9).into(),
             ..Default::default()
-        }).with_children(|parent| {
-            for row in 0..6 {
-                for col in 0..5 {
+        }).insert(Themed::Panel).with_children(|parent| {
+            for row in 0..config.max_guesses {
+                for col in 0..config.word_length {
                     parent
                         .spawn_bundle(NodeBundle {
                             style: Style {
                                 position_type: PositionType::Relative,
-                                max_size: Size::new(Val::Px(40.), Val::Px(40.)),
-                                size: Size::new(Val::Px(40.), Val::Percent(40.0)),
-                                margin: Rect {
-                                    top: Val::Px(5.),
-                                    left: Val::Px(5.),
-                                    right: Val::Px(5.),
-                                    bottom: Val::Px(5.),
-                                },
+                                max_size: Size::new(Val::Px(tile_size), Val::Px(tile_size)),
+                                size: Size::new(Val::Px(tile_size), Val::Percent(40.0)),
+                                margin: Rect::all(Val::Px(sizes.gap)),
                                 align_items: AlignItems::Center,
                                 justify_content: JustifyContent::Center,
                                 align_content: AlignContent::Center,
                                 ..Default::default()
                             },
-                            color: Color::rgb(0.15, 0.15, 0.15).into(),
                             ..Default::default()
                         })
                     .with_children(|parent| {
                         // text
                         parent.spawn_bundle(TextBundle {
                             style: Style {
-                                margin: Rect::all(Val::Px(5.0)),
+                                margin: Rect::all(Val::Px(sizes.gap)),
                                 ..Default::default()
                             },
                             text: Text::with_section(
                                       "",
                                       TextStyle {
                                           font: asset_server.load("fonts/FiraCode-Bold.ttf"),
-                                          font_size: 30.0,
-                                          color: Color::WHITE,
+                                          font_size: tile_size * sizes.tile_font,
+                                          ..Default::default()
                                       },
                                       Default::default(),
                                   ),
                                   ..Default::default()
                         }).insert(Cell {
-                            row: row as usize,
-                            column: col as usize,
-                            guess: None
-                        });
+                            row,
+                            column: col,
+                            guess: None,
+                            score: None,
+                        }).insert(Themed::TileText);
+                        // The UI's y axis points up, so `bottom` is the top edge.
+                        parent.spawn_bundle(TextBundle {
+                            focus_policy: bevy::ui::FocusPolicy::Pass,
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                position: Rect {
+                                    bottom: Val::Px(sizes.tight_gap),
+                                    right: Val::Px(sizes.tight_gap),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::with_section(
+                                      "",
+                                      TextStyle {
+                                          font: asset_server.load("fonts/FiraCode-Bold.ttf"),
+                                          font_size: tile_size * sizes.marker_font,
+                                          ..Default::default()
+                                      },
+                                      Default::default(),
+                                  ),
+                                  ..Default::default()
+                        }).insert(Marker).insert(Themed::TileText);
                     });
                 }
             }
@@ -467,7 +1078,7 @@ fn spawn_grid(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
     });
 }
 
-fn spawn_score(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+fn spawn_score(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, sizes: &Sizes) {
     parent.spawn_bundle(NodeBundle {
         style: Style {
             position_type: PositionType::Relative,
@@ -481,49 +1092,184 @@ fn spawn_score(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
             align_content: AlignContent::Center,
             ..Default::default()
         },
-        color: Color::rgb(0.9, 0.9, 0.9).into(),
         ..Default::default()
-    }).with_children(|parent| {
+    }).insert(Themed::Panel).with_children(|parent| {
         parent.spawn_bundle(NodeBundle {
             style: Style {
                 position_type: PositionType::Relative,
                 position: Rect {
                     ..Default::default()
                 },
-                margin: Rect::all(Val::Px(20.0)),
+                margin: Rect::all(Val::Px(2. * sizes.padding)),
                 ..Default::default()
             },
-            color: Color::rgb(0.0, 0.0, 0.0).into(),
             ..Default::default()
-        }).with_children(|parent| {
+        }).insert(Themed::Button).with_children(|parent| {
             // text
             parent.spawn_bundle(TextBundle {
                 style: Style {
-                    margin: Rect::all(Val::Px(5.0)),
+                    margin: Rect::all(Val::Px(sizes.gap)),
                     ..Default::default()
                 },
                 text: Text::with_section(
                           "0",
                           TextStyle {
                               font: asset_server.load("fonts/FiraCode-Bold.ttf"),
-                              font_size: 30.0,
-                              color: Color::WHITE,
+                              font_size: sizes.heading_font,
+                              ..Default::default()
                           },
                           Default::default(),
                       ),
                       ..Default::default()
-            }).insert(Score {});
+            }).insert(Score {}).insert(Themed::TileText);
         });
+        parent.spawn_bundle(TextBundle {
+            style: Style {
+                margin: Rect::all(Val::Px(sizes.gap)),
+                ..Default::default()
+            },
+            text: Text::with_section(
+                      "",
+                      TextStyle {
+                          font: asset_server.load("fonts/FiraCode-Bold.ttf"),
+                          font_size: sizes.font,
+                          ..Default::default()
+                      },
+                      Default::default(),
+                  ),
+                  ..Default::default()
+        }).insert(PuzzleInfo).insert(Themed::Text);
     });
 }
 
-fn spawn_container(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let game_entity = commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    ..Default::default()
+fn spawn_settings(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, settings: &Settings, sizes: &Sizes) {
+    parent.spawn_bundle(NodeBundle {
+        style: Style {
+            position_type: PositionType::Relative,
+            flex_direction: FlexDirection::Row,
+            // Buttons that don't fit on one line wrap onto the next one down.
+            flex_wrap: FlexWrap::WrapReverse,
+            size: Size::new(Val::Percent(100.), Val::Auto),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..Default::default()
+        },
+        ..Default::default()
+    }).insert(Themed::Background).with_children(|parent| {
+        for setting in [
+            SettingButton::WordLength,
+            SettingButton::MaxGuesses,
+            SettingButton::HardMode,
+            SettingButton::Mode,
+            SettingButton::Language,
+            SettingButton::Layout,
+            SettingButton::Motion,
+            SettingButton::Theme,
+            SettingButton::Stats,
+            SettingButton::Suggest,
+        ] {
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        position_type: PositionType::Relative,
+                        margin: Rect::all(Val::Px(sizes.gap)),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(setting)
+                .insert(Themed::Button)
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        style: Style {
+                            margin: Rect::all(Val::Px(sizes.gap)),
+                            ..Default::default()
+                        },
+                        text: Text::with_section(
+                                  setting.label(settings),
+                                  TextStyle {
+                                      font: asset_server.load("fonts/FiraCode-Bold.ttf"),
+                                      font_size: sizes.font,
+                                      ..Default::default()
+                                  },
+                                  Default::default(),
+                              ),
+                              ..Default::default()
+                    }).insert(Themed::TileText);
+                });
+        }
+    });
+}
+
+fn spawn_message(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, sizes: &Sizes) {
+    parent.spawn_bundle(TextBundle {
+        style: Style {
+            margin: Rect::all(Val::Px(sizes.gap)),
+            ..Default::default()
+        },
+        text: Text::with_section(
+                  "",
+                  TextStyle {
+                      font: asset_server.load("fonts/FiraCode-Bold.ttf"),
+                      font_size: sizes.font,
+                      ..Default::default()
+                  },
+                  Default::default(),
+              ),
+              ..Default::default()
+    }).insert(Message).insert(Themed::Text);
+}
+
+fn spawn_container(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    state: Res<GameState>,
+    custom_assets: Res<Assets<CustomAsset>>,
+    theme: Res<Theme>,
+    windows: Res<Windows>,
+) {
+    let alphabet = custom_assets.get(&state.handle)
+        .map_or_else(Vec::new, |dict| dict.dictionary.alphabet().to_vec());
+    let fit = fit_to_window(&windows, &theme, &settings, &alphabet);
+    let game_container_entity = spawn_game(&mut commands, &asset_server, &settings, &alphabet, &fit);
+    commands.insert_resource(GameContainer {
+        game_container_entity,
+        fit,
+    })
+}
+
+/// The sizes and arrangement that fit a round of `settings` into the window.
+fn fit_to_window(windows: &Windows, theme: &Theme, settings: &Settings, alphabet: &[char]) -> Fit {
+    let (width, height) = windows.get_primary().map_or_else(window_size, |window| (window.width(), window.height()));
+    let rows = keyboard_rows(alphabet, settings.layout());
+    let widest = rows.iter().map(|row| row.len()).max().unwrap_or(0).max(KEYS_PER_ROW);
+    Fit::new(
+        &theme.sizes,
+        width,
+        height,
+        (settings.config.word_length, settings.config.max_guesses),
+        (widest, rows.len()),
+    )
+}
+
+/// Builds the keyboard, board and settings for a round of `settings`.
+fn spawn_game(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    settings: &Settings,
+    alphabet: &[char],
+    fit: &Fit,
+) -> Entity {
+    let sizes = &fit.sizes;
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    ..Default::default()
                 },
                 size: Size::new(Val::Percent(100.), Val::Percent(100.)),
                 align_items: AlignItems::Center,
@@ -532,95 +1278,169 @@ fn spawn_container(mut commands: Commands, asset_server: Res<AssetServer>) {
                 align_content: AlignContent::Center,
                 ..Default::default()
             },
-            color: Color::rgb(1.0, 1.0, 1.0).into(),
             ..Default::default()
-        }).with_children(|parent| {
-            spawn_keyboard(parent, &asset_server);
-            spawn_grid(parent, &asset_server);
-            spawn_score(parent, &asset_server);
-        }).id();
-
-    commands.insert_resource(GameContainer {
-        game_container_entity: game_entity
-    })
+        }).insert(Themed::Background).with_children(|parent| {
+            // Keyboard first, so it ends up under the grid or to its right.
+            parent.spawn_bundle(NodeBundle {
+                style: Style {
+                    flex_direction: match fit.arrangement {
+                        Arrangement::Portrait => FlexDirection::Column,
+                        Arrangement::Landscape => FlexDirection::RowReverse,
+                    },
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                color: Color::NONE.into(),
+                ..Default::default()
+            }).with_children(|parent| {
+                parent.spawn_bundle(NodeBundle {
+                    style: Style {
+                        margin: Rect::all(Val::Px(sizes.padding)),
+                        ..Default::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..Default::default()
+                }).insert(KeyboardSlot).with_children(|parent| {
+                    spawn_keyboard(parent, asset_server, alphabet, settings.layout(), sizes);
+                });
+                parent.spawn_bundle(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..Default::default()
+                }).with_children(|parent| {
+                    spawn_message(parent, asset_server, sizes);
+                    spawn_grid(parent, asset_server, &settings.config, sizes);
+                });
+            });
+            spawn_score(parent, asset_server, sizes);
+            spawn_settings(parent, asset_server, settings, sizes);
+        }).id()
 }
 
-fn setup(mut state: ResMut<GameState>, asset_server: Res<AssetServer>) {
-    state.handle = asset_server.load("words.dict");
+/// Rebuilds the board when it no longer fits, because the window was resized
+/// or the theme's sizes were edited, keeping the round in progress.
+fn restyle_game(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
+    mut game_container: ResMut<GameContainer>,
+    mut state: ResMut<GameState>,
+    custom_assets: Res<Assets<CustomAsset>>,
+    windows: Res<Windows>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    if !windows.is_changed() && !theme.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let alphabet = match custom_assets.get(&state.handle) {
+        Some(dict) => dict.dictionary.alphabet().to_vec(),
+        None => return,
+    };
+    let fit = fit_to_window(&windows, &theme, &settings, &alphabet);
+    if fit == game_container.fit {
+        return;
+    }
+    // Loading redraws the guesses made so far onto the new tiles.
+    if app_state.set(AppState::Loading).is_err() {
+        return;
+    }
+    commands.entity(game_container.game_container_entity).despawn_recursive();
+    game_container.game_container_entity = spawn_game(&mut commands, &asset_server, &settings, &alphabet, &fit);
+    game_container.fit = fit;
+    // Letters typed into the current row go with its tiles.
+    state.column = 0;
+    state.guess = String::new();
 }
 
-fn score_letter(state: &ResMut<GameState>, letter: &char, row: usize, col: usize) -> Option<u32> {
-    if state.row == row {
-        return None
-    } else {
-        let mut score = 0;
-        let word = state.word.clone().expect("Word not set!");
-        let mut chars = word.chars();
-        if chars.any(|c| {
-            c == *letter
-        }) {
-            score += 1;
-        }
+/// Starts loading the themes, and watches the asset folder so that edited
+/// theme files are picked up while the game runs.
+fn setup_theme(mut commands: Commands, mut settings: ResMut<Settings>, asset_server: Res<AssetServer>) {
+    if let Err(err) = asset_server.watch_for_changes() {
+        println!("{}", err);
+    }
+    let themes = Themes::load(&asset_server, &settings.theme);
+    if settings.theme != themes.name() {
+        settings.theme = themes.name().to_string();
+    }
+    commands.insert_resource(themes);
+}
 
-        let pos = word.chars().nth(col);
+fn setup(mut state: ResMut<GameState>, settings: Res<Settings>, asset_server: Res<AssetServer>) {
+    state.handle = asset_server.load(settings.dictionary_path());
+}
 
-        if let Some(p) = pos {
-            if p == *letter {
-                score += 1;
-            }
-        }
-        
-        Some(score)
+fn key_score(state: &GameState, key: &Key) -> Option<LetterScore> {
+    match (&state.game, key.key.chars().next()) {
+        (Some(game), Some(c)) => game.key_score(c),
+        _ => None,
     }
 }
 
-fn score_key(state: &ResMut<GameState>, letter: &String) -> Option<u8> {
-    if let Some(word) = &state.word {
-        let guesses: String = state.guesses.iter().fold(String::new(), |mut guess_set, guess| {
-            guess_set.push_str(guess.as_str());
-            guess_set
-        });
-        let letter_in_guesses = letter.chars().any(|c| {
-            guesses.chars().any(|g| {
-                g == c
-            })
-        });
-        let has_word = word.chars().any(|c| {
-            letter.chars().any(|g| {
-                g == c
-            })
-        });
-        if letter_in_guesses && !has_word {
-            return Some(1)
+/// Turns the tiles of the submitted row over one after another, showing each
+/// colour halfway through. The keyboard and win check wait until every tile is
+/// done; with reduced motion the row is coloured straight away.
+fn reveal_row(
+    time: Res<Time>,
+    mut state: ResMut<GameState>,
+    settings: Res<Settings>,
+    mut cell_query: Query<(&Parent, &mut Cell)>,
+    mut tile_query: Query<&mut Transform>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    let state = &mut *state;
+    let (game, reveal) = match (&state.game, state.reveal.as_mut()) {
+        (Some(game), Some(reveal)) if !settings.reduced_motion => (game, reveal),
+        _ => {
+            state.reveal = None;
+            app_state.set(AppState::Loading).expect("Failed to transition to loading");
+            return;
         }
-        if letter_in_guesses && has_word {
-            return Some(2)
+    };
+    reveal.timer.tick(time.delta());
+    let elapsed = reveal.timer.elapsed_secs();
+    if let Some(guess) = game.guesses().get(reveal.row) {
+        for (parent, mut cell) in cell_query.iter_mut() {
+            if cell.row != reveal.row {
+                continue;
+            }
+            let progress = ((elapsed - cell.column as f32 * FLIP_STAGGER) / FLIP_SECONDS).clamp(0., 1.);
+            if let Ok(mut transform) = tile_query.get_mut(parent.0) {
+                // Squashing the tile flat and back reads as it turning over.
+                transform.scale.y = (progress * std::f32::consts::PI).cos().abs();
+            }
+            let score = guess.scores.get(cell.column).copied();
+            if progress >= 0.5 && cell.score != score {
+                cell.score = score;
+            }
         }
-        None
-    } else {
-        None 
+    }
+    if reveal.timer.finished() {
+        state.reveal = None;
+        // Loading paints the keyboard, then hands over to the win check.
+        app_state.set(AppState::Loading).expect("Failed to transition to loading");
     }
 }
 
-
 fn check_keyboard(
     mut key_query: Query<( &Parent, &mut Text, &Key)>,
     mut p_query: Query<&mut UiColor>,
     state: ResMut<GameState>,
+    theme: Res<Theme>,
     mut app_state: ResMut<State<AppState>>,
 ) {
     for ( parent, _, key) in key_query.iter_mut() {
-        let score = score_key(&state, &key.key);
+        let score = key_score(&state, key);
         let parent_style = p_query.get_mut(parent.0);
         match parent_style {
             Ok(mut p) => {
-                if let Some( s ) = score {
-                    if s == 1 {
-                        p.0 = Color::RED;
-                    } 
-                    if s == 2 {
-                        p.0 = Color::GREEN;
-                    }
+                if let Some(s) = score {
+                    p.0 = theme.score_color(s);
                 }
             }
             Err(err) => {
@@ -632,36 +1452,47 @@ fn check_keyboard(
 }
 
 fn check_guesses(
-    mut text_query: Query<( &Parent, &mut Text, &Cell )>,
-    mut p_query: Query<&mut UiColor>,
+    mut text_query: Query<( &mut Text, &mut Cell )>,
     state: ResMut<GameState>,
     mut app_state: ResMut<State<AppState>>,
 ) {
-    for ( parent, mut text, cell ) in text_query.iter_mut() {
-        if let Some(g) = &cell.guess {
-            let score = score_letter(&state, g, cell.row, cell.column);
+    let guesses = state.game.as_ref().map_or(&[][..], |game| game.guesses());
+    for ( mut text, mut cell ) in text_query.iter_mut() {
+        // Submitted rows come from the game itself, so a restored round is drawn too.
+        let guess = guesses.get(cell.row);
+        if let Some(g) = guess.and_then(|guess| guess.word.chars().nth(cell.column)).or(cell.guess) {
+            let score = guess.and_then(|guess| guess.scores.get(cell.column)).copied();
             text.sections[0].value = g.to_string();
-            let parent_style = p_query.get_mut(parent.0);
-            match parent_style {
-                Ok(mut p) => {
-                    if let Some(s) = score {
-                        if s == 1 {
-                            p.0 = Color::rgb(0.8, 0.8, 0.0);
-                        }
-                        if s == 2 {
-                            p.0 = Color::GREEN;
-                        }
-                    }
-                }
-                Err(err) => {
-                    println!("{}", err);
-                }
+            if cell.score != score {
+                cell.score = score;
             }
         }
     }
     app_state.set(AppState::CheckWin);
 }
 
+/// Colours tiles by the score they show, and sets their markers.
+fn paint_tiles(
+    theme: Res<Theme>,
+    cell_query: Query<(&Parent, &Cell, ChangeTrackers<Cell>)>,
+    mut tile_query: Query<(&mut UiColor, &Children)>,
+    mut marker_query: Query<&mut Text, With<Marker>>,
+) {
+    for (parent, cell, tracker) in cell_query.iter() {
+        if !theme.is_changed() && !tracker.is_changed() {
+            continue;
+        }
+        if let Ok((mut color, children)) = tile_query.get_mut(parent.0) {
+            color.0 = cell.score.map_or(theme.tile, |score| theme.score_color(score));
+            for &child in children.iter() {
+                if let Ok(mut marker) = marker_query.get_mut(child) {
+                    marker.sections[0].value = theme.marker(cell.score).to_string();
+                }
+            }
+        }
+    }
+}
+
 fn update_text(
     mut text_query: Query<( &mut Text, &Cell ), Changed<Cell>>,
 ) {
@@ -672,18 +1503,93 @@ fn update_text(
     }
 }
 
+fn update_message(
+    mut text_query: Query<&mut Text, With<Message>>,
+    state: Res<GameState>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = state.message.clone().unwrap_or_default();
+    }
+}
+
+/// Wobbles the row of a rejected guess from side to side, then takes the
+/// reason away again once it has been up for a moment.
+fn shake_rejected_row(
+    time: Res<Time>,
+    mut state: ResMut<GameState>,
+    settings: Res<Settings>,
+    cell_query: Query<(&Parent, &Cell)>,
+    mut style_query: Query<&mut Style>,
+) {
+    // Only borrow the state mutably while there is something to animate, so
+    // it isn't flagged as changed every frame.
+    if state.rejected.is_none() {
+        return;
+    }
+    let rejection = state.rejected.as_mut().expect("Checked above");
+    rejection.timer.tick(time.delta());
+    let elapsed = rejection.timer.elapsed_secs();
+    let offset = if elapsed < SHAKE_SECONDS && !settings.reduced_motion {
+        let fade = 1. - elapsed / SHAKE_SECONDS;
+        Val::Px((elapsed * 40.).sin() * 8. * fade)
+    } else {
+        Val::Undefined
+    };
+    let row = rejection.row;
+    for (parent, cell) in cell_query.iter() {
+        if cell.row == row {
+            if let Ok(mut style) = style_query.get_mut(parent.0) {
+                style.position.left = offset;
+            }
+        }
+    }
+    if rejection.timer.finished() {
+        state.rejected = None;
+        state.message = None;
+    }
+}
+
+fn today() -> Date {
+    let now = chrono::Local::now();
+    Date::new(now.year(), now.month(), now.day())
+}
+
+fn next_puzzle_countdown() -> String {
+    let now = chrono::Local::now().naive_local();
+    let midnight = now.date().succ_opt().and_then(|day| day.and_hms_opt(0, 0, 0));
+    let seconds = midnight.map_or(0, |midnight| (midnight - now).num_seconds().max(0));
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn update_puzzle_info(
+    mut text_query: Query<&mut Text, With<PuzzleInfo>>,
+    state: Res<GameState>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = match (state.puzzle, state.code) {
+            (Some(number), _) => format!("#{} next in {}", number, next_puzzle_countdown()),
+            (None, Some(code)) => format!("code {}", code),
+            (None, None) => String::new(),
+        };
+    }
+}
+
 fn update_score(
     mut text_query: Query<( &mut Text, &Score)>,
-    game_state: Res<GameState>
+    stats: Res<Stats>
 ) {
     for ( mut text, _) in text_query.iter_mut() {
-        text.sections[0].value = format!("{}", game_state.wins);
+        text.sections[0].value = format!("{}", stats.current_streak);
     }
 }
 
 fn handle_letter(query: &mut Query<&mut Cell>, state: &mut ResMut<GameState>, letter: char) {
+    let row = state.row();
     let cell = query.iter_mut().find(|cell| {
-        cell.row == state.row && cell.column == state.column
+        cell.row == row && cell.column == state.column
     });
     if let Some(mut c) = cell {
         (*c).guess = Some(letter);
@@ -700,6 +1606,7 @@ fn handle_keyboard(
     mut query: Query<&mut Cell>,
     mut state: ResMut<GameState>,
     mut key_evr: EventReader<KeyboardInput>,
+    mut char_evr: EventReader<ReceivedCharacter>,
     custom_assets: ResMut<Assets<CustomAsset>>,
     mut app_state: ResMut<State<AppState>>,
     ) {
@@ -716,84 +1623,6 @@ fn handle_keyboard(
                             state.column -= 1;
                         }
                     }
-                    Some(KeyCode::A) => {
-                        handle_letter(&mut query, &mut state, 'a');
-                    }
-                    Some(KeyCode::B) => {
-                        handle_letter(&mut query, &mut state, 'b');
-                    }
-                    Some(KeyCode::C) => {
-                        handle_letter(&mut query, &mut state, 'c');
-                    }
-                    Some(KeyCode::D) => {
-                        handle_letter(&mut query, &mut state, 'd');
-                    }
-                    Some(KeyCode::E) => {
-                        handle_letter(&mut query, &mut state, 'e');
-                    }
-                    Some(KeyCode::F) => {
-                        handle_letter(&mut query, &mut state, 'f');
-                    }
-                    Some(KeyCode::G) => {
-                        handle_letter(&mut query, &mut state, 'g');
-                    }
-                    Some(KeyCode::H) => {
-                        handle_letter(&mut query, &mut state, 'h');
-                    }
-                    Some(KeyCode::I) => {
-                        handle_letter(&mut query, &mut state, 'i');
-                    }
-                    Some(KeyCode::J) => {
-                        handle_letter(&mut query, &mut state, 'j');
-                    }
-                    Some(KeyCode::K) => {
-                        handle_letter(&mut query, &mut state, 'k');
-                    }
-                    Some(KeyCode::L) => {
-                        handle_letter(&mut query, &mut state, 'l');
-                    }
-                    Some(KeyCode::M) => {
-                        handle_letter(&mut query, &mut state, 'm');
-                    }
-                    Some(KeyCode::N) => {
-                        handle_letter(&mut query, &mut state, 'n');
-                    }
-                    Some(KeyCode::O) => {
-                        handle_letter(&mut query, &mut state, 'o');
-                    }
-                    Some(KeyCode::P) => {
-                        handle_letter(&mut query, &mut state, 'p');
-                    }
-                    Some(KeyCode::Q) => {
-                        handle_letter(&mut query, &mut state, 'q');
-                    }
-                    Some(KeyCode::R) => {
-                        handle_letter(&mut query, &mut state, 'r');
-                    }
-                    Some(KeyCode::S) => {
-                        handle_letter(&mut query, &mut state, 's');
-                    }
-                    Some(KeyCode::T) => {
-                        handle_letter(&mut query, &mut state, 't');
-                    }
-                    Some(KeyCode::U) => {
-                        handle_letter(&mut query, &mut state, 'u');
-                    }
-                    Some(KeyCode::V) => {
-                        handle_letter(&mut query, &mut state, 'v');
-                    }
-                    Some(KeyCode::W) => {
-                        handle_letter(&mut query, &mut state, 'w');
-                    }
-                    Some(KeyCode::X) => {
-                        handle_letter(&mut query, &mut state, 'x');
-                    }
-                    Some(KeyCode::Y) => {
-                        handle_letter(&mut query, &mut state, 'y');
-                    }
-                    Some(KeyCode::Z) => {
-                        handle_letter(&mut query, &mut state, 'z');
-                    }
                     Some(KeyCode::Return) => {
                         submit_guess(&mut state, &custom_assets, &mut app_state);
                     }
@@ -805,6 +1634,19 @@ fn handle_keyboard(
             }
         }
     }
+
+    // Letters come in as text so that layouts and dead keys produce ä, ñ, ł and friends.
+    let alphabet = match custom_assets.get(&state.handle) {
+        Some(dict) => dict.dictionary.alphabet().to_vec(),
+        None => return,
+    };
+    for ev in char_evr.iter() {
+        for letter in ev.char.to_lowercase() {
+            if alphabet.contains(&letter) {
+                handle_letter(&mut query, &mut state, letter);
+            }
+        }
+    }
 }
 
 fn submit_guess(
@@ -812,16 +1654,31 @@ fn submit_guess(
     custom_assets: &ResMut<Assets<CustomAsset>>,
     app_state: &mut ResMut<State<AppState>>,
     ) {
-    if state.column == 5 {
-        let custom_asset = custom_assets.get(&state.handle);
-
-        if let Some(dict) = custom_asset {
-            if dict.words.contains(&state.guess) {
-                let guess = state.guess.clone();
-                state.guesses.push(guess);
-                state.row += 1;
+    let state = &mut **state;
+    let custom_asset = custom_assets.get(&state.handle);
+
+    if let (Some(dict), Some(game)) = (custom_asset, state.game.as_mut()) {
+        let row = game.guesses().len();
+        match game.submit(&state.guess, &dict.dictionary) {
+            Ok(_) => {
                 state.column = 0;
-                app_state.set(AppState::Loading);
+                state.message = None;
+                let flips = FLIP_STAGGER * (game.config().word_length - 1) as f32 + FLIP_SECONDS;
+                state.reveal = Some(Reveal {
+                    row,
+                    timer: Timer::from_seconds(flips, false),
+                });
+                app_state.set(AppState::Revealing);
+            }
+            Err(err) => {
+                state.message = Some(match err {
+                    GuessError::WrongLength { expected, found } if found < expected => "not enough letters".to_string(),
+                    err => err.to_string(),
+                });
+                state.rejected = Some(Rejection {
+                    row,
+                    timer: Timer::from_seconds(TOAST_SECONDS, false),
+                });
             }
         }
     }
@@ -829,60 +1686,206 @@ fn submit_guess(
 
 fn init_game(
     mut state: ResMut<GameState>,
+    mut settings: ResMut<Settings>,
     custom_assets: ResMut<Assets<CustomAsset>>,
+    themes: Res<Themes>,
+    store: Res<Store>,
     mut app_state: ResMut<State<AppState>>
     ) {
+    // The board is built from the theme's sizes, so wait for it as well.
+    if !themes.is_loaded() {
+        return;
+    }
     let custom_asset = custom_assets.get(&state.handle);
 
     if let Some(dict) = custom_asset {
-        let num = rand::thread_rng().gen_range(0, dict.words.len() as i32);
-        if let Some(word) = dict.words.get(num as usize) {
-            state.word = Some(word.clone());
-            app_state.set(AppState::Playing).expect("Could not start game!");
+        // Today's daily puzzle is only dealt once. However daily mode is
+        // entered, it picks up where it was left or shows its result again.
+        let daily = match settings.mode {
+            Mode::Daily => store.load::<SavedRound>(&daily_key(&settings.language)),
+            _ => None,
+        };
+        for round in state.resume.take().into_iter().chain(daily) {
+            if let Some(game) = resume_round(&round, &dict.dictionary, &settings) {
+                state.puzzle = round.puzzle;
+                state.code = round.code;
+                state.recorded = game.status() != Status::InProgress;
+                state.game = Some(game);
+                settings.config = round.game.config;
+                settings.mode = round.mode;
+                // Paint the restored guesses before carrying on.
+                app_state.set(AppState::Loading).expect("Could not resume game!");
+                return;
+            }
         }
+        match pick_answer(&dict.dictionary, settings.config, settings.mode, today(), rand::thread_rng().gen()) {
+            Ok(pick) => {
+                state.puzzle = pick.puzzle;
+                state.code = Some(pick.code);
+                state.game = Some(Game::new(pick.answer, pick.config));
+                state.recorded = false;
+                settings.config = pick.config;
+                app_state.set(AppState::Playing).expect("Could not start game!");
+            }
+            Err(err @ PickError::UnknownCode(_)) => {
+                // The code came from a different word list; play something rather than nothing.
+                state.message = Some(err.to_string());
+                settings.mode = Mode::Random;
+            }
+            Err(_) => {}
+        }
+    }
+
+}
+
+/// The saved round to pick up, if it still applies.
+///
+/// Rounds from another language, a stale daily puzzle or a launch that asks
+/// for a particular puzzle are dropped. Finished rounds are only kept for
+/// today's daily puzzle, so it shows its result instead of being played again.
+fn resume_round(round: &SavedRound, dictionary: &Dictionary, settings: &Settings) -> Option<Game> {
+    if round.language != settings.language || matches!(settings.mode, Mode::Seeded(_) | Mode::Code(_)) {
+        return None;
+    }
+    if round.puzzle.is_some() && round.puzzle != daily::puzzle_number(today()) {
+        return None;
     }
+    let game = Game::restore(&round.game, dictionary)?;
+    let daily = round.puzzle.is_some() && settings.mode == Mode::Daily;
+    if game.status() != Status::InProgress && !daily {
+        return None;
+    }
+    Some(game)
+}
 
+/// Where today's daily round in `language` is saved.
+fn daily_key(language: &str) -> String {
+    format!("daily-{}", language)
 }
 
 fn check_win(
     mut state: ResMut<GameState>,
+    mut stats: ResMut<Stats>,
+    settings: Res<Settings>,
+    store: Res<Store>,
     mut app_state: ResMut<State<AppState>>,
     ) {
-    if let Some(w) = &state.word {
-        if w.eq(&state.guess) {
-            app_state.set(AppState::Win);
-        } else if state.row == 6 {
-            app_state.set(AppState::Fail);
-        } else {
-            app_state.set(AppState::Playing);
+    let state = &mut *state;
+    if let Some(game) = &state.game {
+        if game.status() != Status::InProgress && !state.recorded {
+            stats.record(game);
+            store.save("stats", &*stats);
+            state.recorded = true;
+        }
+        let round = SavedRound {
+            game: game.save(),
+            language: settings.language.clone(),
+            mode: settings.mode,
+            puzzle: state.puzzle,
+            code: state.code,
+        };
+        store.save("round", &round);
+        if round.puzzle.is_some() {
+            // Kept apart from other rounds, so playing those can't free the
+            // daily puzzle up to be played again.
+            store.save(&daily_key(&settings.language), &round);
         }
+        match game.status() {
+            Status::Won => app_state.set(AppState::Win),
+            Status::Lost => app_state.set(AppState::Fail),
+            Status::InProgress => app_state.set(AppState::Playing),
+        };
         state.guess = String::new();
     }
 }
 
+fn launch_mode() -> Mode {
+    for (key, value) in launch_options() {
+        match key.as_str() {
+            "daily" => return Mode::Daily,
+            "seed" => match value.parse() {
+                Ok(seed) => return Mode::Seeded(seed),
+                Err(err) => println!("Invalid seed {}: {}", value, err),
+            },
+            "code" => match value.parse() {
+                Ok(code) => return Mode::Code(code),
+                Err(err) => println!("Invalid code {}: {}", value, err),
+            },
+            _ => {}
+        }
+    }
+    Mode::default()
+}
+
+/// `--key value` or `--key=value` pairs from the command line.
+#[cfg(not(target_arch = "wasm32"))]
+fn launch_options() -> Vec<(String, String)> {
+    let mut options = Vec::new();
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        if let Some(key) = arg.strip_prefix("--") {
+            match key.split_once('=') {
+                Some((key, value)) => options.push((key.to_string(), value.to_string())),
+                None => {
+                    let value = args.next_if(|value| !value.starts_with("--")).unwrap_or_default();
+                    options.push((key.to_string(), value));
+                }
+            }
+        }
+    }
+    options
+}
+
+/// `?key=value` pairs from the page URL.
+#[cfg(target_arch = "wasm32")]
+fn launch_options() -> Vec<(String, String)> {
+    let search = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default();
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// The size to open the window at: the whole page on the web, so the game
+/// fits phone screens.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn run(word: String) -> Result<(), ()> {
-    Ok(())
+fn window_size() -> (f32, f32) {
+    (600., 700.)
 }
 
-#[wasm_bindgen]
 #[cfg(target_arch = "wasm32")]
-pub fn run(word: String) -> Result<(), JsValue> {
-    // Use `web_sys`'s global `window` function to get a handle on the global
-    // window object.
-    // note the double unwrap because local_storage() returns Result<Option<...>, ...>
-    let local_storage = web_sys::window().unwrap().local_storage().unwrap().unwrap();
+fn window_size() -> (f32, f32) {
+    web_sys::window()
+        .and_then(|window| Some((window.inner_width().ok()?.as_f64()?, window.inner_height().ok()?.as_f64()?)))
+        .map_or((600., 700.), |(width, height)| (width as f32, height as f32))
+}
 
-    let value = local_storage.set_item("word", word.as_str()).unwrap();
-    // Manufacture the element we're gonna append
-    
-    Ok(())
+/// Keeps the canvas the size of the page, e.g. when a phone is turned.
+#[cfg(target_arch = "wasm32")]
+fn follow_browser_window(mut windows: ResMut<Windows>) {
+    let (width, height) = window_size();
+    let resized = windows.get_primary().map_or(false, |window| window.width() != width || window.height() != height);
+    if let (true, Some(window)) = (resized, windows.get_primary_mut()) {
+        window.set_resolution(width, height);
+    }
 }
 
+/// Native windows resize themselves.
+#[cfg(not(target_arch = "wasm32"))]
+fn follow_browser_window() {}
+
 fn show_win_notice(
     mut query: Query<(With<WinNotice>, &Children)>,
     mut q_text: Query<&mut Text>, 
     state: Res<GameState>,
+    stats: Res<Stats>,
     app_state: Res<State<AppState>>,
     ) {
     for ( _, children) in query.iter_mut() {
@@ -890,38 +1893,36 @@ fn show_win_notice(
             let text = q_text.get_mut(child);
             if let Ok(mut t) = text {
                 if *app_state.current() == AppState::Win{
-                    t.sections[0].value = format!("The word was: {}. Congrats!\nStreak {} (nice!)\n(click)", state.word.clone().unwrap(), state.wins + 1);
+                    t.sections[0].value = format!("The word was: {}. Congrats!\nStreak {} (nice!)\n(click)", state.answer(), stats.current_streak);
 
                 } else {
-                    t.sections[0].value = format!("Oh no! The word was: {}\n(click)", state.word.clone().unwrap());
+                    t.sections[0].value = format!("Oh no! The word was: {}\n(click)", state.answer());
+                }
+                if state.puzzle.is_some() {
+                    t.sections[0].value.push_str(&format!("\nNext puzzle in {}", next_puzzle_countdown()));
                 }
             }
         }
     }
-    if let Err(er) = run(state.word.clone().unwrap()) {
-        println!("Oh no!");
-
-    }
 }
 
 fn handle_accept_win(
     mut interaction_query: Query<
         (&Interaction, &Children),
-        (Changed<Interaction>, With<Button>),
+        (Changed<Interaction>, With<WinNotice>),
     >,
     mut app_state: ResMut<State<AppState>>,
     mut game_state: ResMut<GameState>,
+    mut settings: ResMut<Settings>,
 ) {
     for (interaction, _) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Clicked => {
-                game_state.row = 0;
                 game_state.column = 0;
-                game_state.guesses = Vec::new();
-                if *app_state.current() == AppState::Win {
-                    game_state.wins += 1;
-                } else {
-                    game_state.wins = 0;
+                // A shared puzzle is played once, and today's daily puzzle
+                // would only show its result again; carry on with random ones.
+                if let Mode::Daily | Mode::Seeded(_) | Mode::Code(_) = settings.mode {
+                    settings.mode = Mode::Random;
                 }
                 app_state.set(AppState::Init).expect("Failed to transition to init");
             }
@@ -931,10 +1932,300 @@ fn handle_accept_win(
     }
 }
 
+/// What the shared result calls this puzzle, so others can play the same one.
+fn share_label(state: &GameState, settings: &Settings) -> String {
+    match (state.puzzle, settings.mode, state.code) {
+        (Some(number), _, _) => format!("#{}", number),
+        (None, Mode::Seeded(seed), _) => format!("seed {}", seed),
+        (None, _, Some(code)) => format!("code {}", code),
+        (None, _, None) => "random".to_string(),
+    }
+}
+
+/// Copies the result when the share button is clicked, and relabels the
+/// button once the copy has gone through or failed.
+fn handle_share(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ShareButton>)>,
+    button_query: Query<&Children, With<ShareButton>>,
+    mut text_query: Query<&mut Text>,
+    mut clipboard: NonSendMut<Clipboard>,
+    state: Res<GameState>,
+    settings: Res<Settings>,
+) {
+    for interaction in interaction_query.iter() {
+        if let (Interaction::Clicked, Some(game)) = (interaction, &state.game) {
+            clipboard.copy(&share_text(&share_label(&state, &settings), game));
+        }
+    }
+    let label = match clipboard.poll() {
+        None => return,
+        Some(Ok(())) => "copied!",
+        Some(Err(err)) => {
+            println!("Could not copy result: {}", err);
+            "could not copy"
+        }
+    };
+    for children in button_query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].value = label.to_string();
+            }
+        }
+    }
+}
+
+fn suggestion_message(solver: &Solver, word: Option<&str>) -> String {
+    match word {
+        Some(word) => format!(
+            "try {} ({:.1} bits, {} words left)",
+            word.to_uppercase(),
+            solver.entropy(word),
+            solver.candidates().len()
+        ),
+        None => "no words left to suggest".to_string(),
+    }
+}
+
+/// Shows a suggestion from the compute pool once it is ready, if the row it
+/// was asked for is still being played.
+fn show_suggestion(pending: Res<PendingSuggestion>, mut state: ResMut<GameState>) {
+    let mut pending = pending.0.lock().expect("Suggestion lock poisoned");
+    let (answer, row, message) = match &*pending {
+        Some((answer, row, receiver)) => match receiver.try_recv() {
+            Err(TryRecvError::Empty) => return,
+            message => (answer, *row, message.ok()),
+        },
+        None => return,
+    };
+    let current = state.game.as_ref().map(|game| (game.answer(), game.guesses().len()));
+    if current == Some((answer.as_str(), row)) {
+        state.message = message;
+    }
+    *pending = None;
+}
+
+/// Swaps in a keyboard with the new layout, keeping the round in progress.
+fn rebuild_keyboard(
+    mut commands: Commands,
+    keyboard_query: Query<(Entity, &Keyboard)>,
+    slot_query: Query<Entity, With<KeyboardSlot>>,
+    settings: Res<Settings>,
+    state: Res<GameState>,
+    custom_assets: Res<Assets<CustomAsset>>,
+    asset_server: Res<AssetServer>,
+    game_container: Res<GameContainer>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let layout = settings.layout();
+    let alphabet = match custom_assets.get(&state.handle) {
+        Some(dict) => dict.dictionary.alphabet().to_vec(),
+        None => return,
+    };
+    for (entity, keyboard) in keyboard_query.iter() {
+        if keyboard.layout == layout.name {
+            continue;
+        }
+        commands.entity(entity).despawn_recursive();
+        for slot in slot_query.iter() {
+            commands.entity(slot).with_children(|parent| {
+                spawn_keyboard(parent, &asset_server, &alphabet, layout, &game_container.fit.sizes);
+            });
+        }
+    }
+}
+
+/// Colours freshly built keys with what is already known about their
+/// letters, and every key once the theme changes.
+fn paint_keys(
+    key_query: Query<(&Parent, &Key, ChangeTrackers<Key>)>,
+    mut p_query: Query<&mut UiColor>,
+    state: Res<GameState>,
+    theme: Res<Theme>,
+) {
+    for (parent, key, tracker) in key_query.iter() {
+        if !theme.is_changed() && !tracker.is_added() {
+            continue;
+        }
+        if let Ok(mut p) = p_query.get_mut(parent.0) {
+            p.0 = key_score(&state, key).map_or(theme.tile, |score| theme.score_color(score));
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>, store: Res<Store>) {
+    if settings.is_changed() {
+        store.save("settings", &*settings);
+    }
+}
+
+fn update_setting_labels(
+    button_query: Query<(&SettingButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (setting, children) in button_query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].value = setting.label(&settings);
+            }
+        }
+    }
+}
+
+fn handle_settings(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    mut themes: ResMut<Themes>,
+    mut theme: ResMut<Theme>,
+    theme_assets: Res<Assets<Theme>>,
+    mut state: ResMut<GameState>,
+    game_container: Res<GameContainer>,
+    custom_assets: Res<Assets<CustomAsset>>,
+    pending: Res<PendingSuggestion>,
+    task_pool: Res<AsyncComputeTaskPool>,
+    mut stats: ResMut<Stats>,
+    store: Res<Store>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    for (interaction, setting) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let restart = match setting {
+            // Today's daily round is saved with the settings it was dealt with,
+            // and would only be picked up again unchanged.
+            SettingButton::WordLength | SettingButton::MaxGuesses | SettingButton::HardMode
+                if settings.mode == Mode::Daily =>
+            {
+                state.message = Some("switch to random mode to change this".to_string());
+                false
+            }
+            SettingButton::WordLength => {
+                let length = settings.config.word_length;
+                settings.config.word_length = if length >= MAX_WORD_LENGTH {
+                    MIN_WORD_LENGTH
+                } else {
+                    length + 1
+                };
+                true
+            }
+            SettingButton::MaxGuesses => {
+                let current = GUESS_OPTIONS.iter().position(|&n| n == settings.config.max_guesses);
+                let next = current.map_or(0, |i| (i + 1) % GUESS_OPTIONS.len());
+                settings.config.max_guesses = GUESS_OPTIONS[next];
+                true
+            }
+            SettingButton::HardMode => {
+                settings.config.hard_mode = !settings.config.hard_mode;
+                true
+            }
+            SettingButton::Mode => {
+                settings.mode = match settings.mode {
+                    Mode::Random => Mode::Daily,
+                    _ => Mode::Random,
+                };
+                true
+            }
+            SettingButton::Language => {
+                let current = LANGUAGE_PACKS.iter().position(|(language, _)| *language == settings.language);
+                let next = current.map_or(0, |i| (i + 1) % LANGUAGE_PACKS.len());
+                settings.language = LANGUAGE_PACKS[next].0.to_string();
+                true
+            }
+            SettingButton::Layout => {
+                let current = layout::LAYOUTS.iter().position(|layout| layout.name == settings.layout);
+                let next = current.map_or(0, |i| (i + 1) % layout::LAYOUTS.len());
+                settings.layout = layout::LAYOUTS[next].name.to_string();
+                // The keyboard is rebuilt in place by `rebuild_keyboard`.
+                false
+            }
+            SettingButton::Motion => {
+                settings.reduced_motion = !settings.reduced_motion;
+                false
+            }
+            SettingButton::Theme => {
+                // A theme that is still loading is applied by `update_theme` once it's ready.
+                if let Some(next) = themes.next(&theme_assets) {
+                    *theme = next.clone();
+                }
+                settings.theme = themes.name().to_string();
+                // Colours change in place; new sizes rebuild the board in `restyle_game`.
+                false
+            }
+            SettingButton::Stats => {
+                let _ = app_state.set(AppState::Stats);
+                false
+            }
+            SettingButton::Suggest => {
+                if let (Some(dict), Some(game)) = (custom_assets.get(&state.handle), &state.game) {
+                    let opener = OPENERS.iter().find(|&&(language, length, word)| {
+                        game.guesses().is_empty()
+                            && language == settings.language
+                            && length == game.config().word_length
+                            && dict.dictionary.is_allowed(word)
+                    });
+                    if let Some((_, _, word)) = opener {
+                        let solver = Solver::new(&dict.dictionary, *game.config());
+                        state.message = Some(suggestion_message(&solver, Some(word)));
+                    } else {
+                        let (sender, receiver) = mpsc::channel();
+                        let (dictionary, round) = (dict.dictionary.clone(), game.clone());
+                        task_pool
+                            .spawn(async move {
+                                let solver = Solver::for_game(&dictionary, &round);
+                                let best = solver.suggest(1).pop();
+                                let _ = sender.send(suggestion_message(&solver, best.map(|best| best.word).as_deref()));
+                            })
+                            .detach();
+                        *pending.0.lock().expect("Suggestion lock poisoned") =
+                            Some((game.answer().to_string(), game.guesses().len(), receiver));
+                        state.message = Some("thinking...".to_string());
+                    }
+                }
+                false
+            }
+        };
+        // Other settings only apply to a fresh round, so throw this one away.
+        if restart && app_state.set(AppState::Init).is_ok() {
+            abandon_round(&state, &mut stats, &store);
+            commands.entity(game_container.game_container_entity).despawn_recursive();
+            state.column = 0;
+            state.guess = String::new();
+            state.message = None;
+            state.rejected = None;
+        }
+        return;
+    }
+}
+
+/// Counts a round that was started but not finished as lost, so a losing
+/// round can't be thrown away to keep the streak. Daily rounds are left
+/// alone: they are saved on their own and picked up again in daily mode.
+fn abandon_round(state: &GameState, stats: &mut Stats, store: &Store) {
+    let started = state.game.as_ref().map_or(false, |game| {
+        game.status() == Status::InProgress && !game.guesses().is_empty()
+    });
+    if started && state.puzzle.is_none() {
+        stats.record_loss();
+        store.save("stats", &*stats);
+        store.save("round", &None::<SavedRound>);
+    }
+}
+
 fn cleanup_win_notice(mut commands: Commands, win_notice_data: Res<WinNoticeMenu>) {
     commands.entity(win_notice_data.win_notice_entity).despawn_recursive();
 }
 
+fn cleanup_stats_menu(mut commands: Commands, stats_menu: Res<StatsMenu>) {
+    commands.entity(stats_menu.stats_entity).despawn_recursive();
+}
+
 fn cleanup_game_container(mut commands: Commands, game_container_entity: Res<GameContainer>) {
     commands.entity(game_container_entity.game_container_entity).despawn_recursive();
 }
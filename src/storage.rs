@@ -0,0 +1,116 @@
+//! Key/value persistence for settings and stats.
+//!
+//! Native builds keep one JSON file per key in the XDG data directory
+//! (`$XDG_DATA_HOME/birdle`, falling back to `~/.local/share/birdle`). The
+//! web build keeps the same JSON in `localStorage`.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub trait Storage: Send + Sync {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&self, key: &str, value: &str) -> anyhow::Result<()>;
+}
+
+/// The storage backend for this platform, as a resource.
+pub struct Store(Box<dyn Storage>);
+
+impl Store {
+    pub fn open() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let backend = FileStorage::new();
+        #[cfg(target_arch = "wasm32")]
+        let backend = LocalStorage;
+        Store(Box::new(backend))
+    }
+
+    /// Reads `key`, or `None` if it was never saved or can't be understood.
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.0.get(key)?;
+        match serde_json::from_str(&value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                println!("Ignoring saved {}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    pub fn save<T: Serialize>(&self, key: &str, value: &T) {
+        let result = serde_json::to_string(value)
+            .map_err(anyhow::Error::from)
+            .and_then(|value| self.0.set(key, &value));
+        if let Err(err) = result {
+            println!("Could not save {}: {}", key, err);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct FileStorage {
+    dir: Option<std::path::PathBuf>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    fn new() -> Self {
+        use std::env::var_os;
+        use std::path::PathBuf;
+
+        let data_dir = var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+            .or_else(|| var_os("APPDATA").map(PathBuf::from));
+        FileStorage {
+            dir: data_dir.map(|dir| dir.join("birdle")),
+        }
+    }
+
+    fn path(&self, key: &str) -> Option<std::path::PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("{}.json", key)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)?).ok()
+    }
+
+    fn set(&self, key: &str, value: &str) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let path = self.path(key).context("No data directory")?;
+        let dir = path.parent().expect("Path is inside the data directory");
+        std::fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+        // Write to the side and rename, so a crash never leaves half a file.
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, value).with_context(|| format!("Writing {}", temp.display()))?;
+        std::fs::rename(&temp, &path).with_context(|| format!("Writing {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        Self::storage()?.get_item(&format!("birdle.{}", key)).ok()?
+    }
+
+    fn set(&self, key: &str, value: &str) -> anyhow::Result<()> {
+        let storage = Self::storage().ok_or_else(|| anyhow::anyhow!("localStorage is not available"))?;
+        storage
+            .set_item(&format!("birdle.{}", key), value)
+            .map_err(|err| anyhow::anyhow!("localStorage refused {}: {:?}", key, err))
+    }
+}
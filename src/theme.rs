@@ -0,0 +1,255 @@
+//! Colours and sizes for the board, keyboard and menus.
+//!
+//! Themes are RON files under `assets/themes/`, so they can be tweaked without
+//! a rebuild; with `filesystem_watcher` on, saving one restyles the running
+//! game. UI nodes take their colour from the current [`Theme`] through a
+//! [`Themed`] role, so switching themes recolours everything in place. Tiles
+//! and keys are coloured by their score instead.
+
+use anyhow::Context;
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use birdle_core::LetterScore;
+use serde::Deserialize;
+
+/// Every built-in theme, in the order settings cycle through them. Each one
+/// is read from `themes/<name>.theme.ron`.
+pub const THEMES: [&str; 4] = ["light", "dark", "colour-blind", "high-contrast"];
+
+/// The light theme, compiled in for when the chosen theme file can't be read,
+/// so the game still starts instead of waiting on it with a blank window.
+const BUILT_IN: &str = include_str!("../assets/themes/light.theme.ron");
+
+/// The current theme is kept as a resource; it stays at its default until the
+/// chosen theme file has loaded.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, TypeUuid)]
+#[uuid = "6f1f0a43-3c55-4f7e-9a57-2d6b8c1e5a90"]
+pub struct Theme {
+    /// Behind everything else.
+    pub background: Color,
+    /// Behind the grid and the keyboard.
+    pub panel: Color,
+    /// Unscored tiles and keys, and buttons.
+    pub tile: Color,
+    /// Letters and labels on tiles, keys and buttons.
+    pub tile_text: Color,
+    /// Messages and labels on the background.
+    pub text: Color,
+    pub absent: Color,
+    pub present: Color,
+    pub correct: Color,
+    /// Whether scored tiles also carry a marker, so they can be told apart
+    /// without relying on colour.
+    pub markers: bool,
+    pub sizes: Sizes,
+}
+
+/// Font sizes and spacing, in pixels unless noted otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Sizes {
+    /// Around keys, tiles, buttons and labels.
+    pub gap: f32,
+    /// Between the lines of the stats screen.
+    pub tight_gap: f32,
+    /// Around the keyboard, the stats and the score.
+    pub padding: f32,
+    pub key: f32,
+    /// The largest a tile gets; rows shrink to fit `grid_height` when there
+    /// are many guesses.
+    pub tile: f32,
+    pub grid_height: f32,
+    pub font: f32,
+    pub heading_font: f32,
+    pub small_font: f32,
+    pub key_font: f32,
+    /// Tile letters and markers, as a fraction of the tile size.
+    pub tile_font: f32,
+    pub marker_font: f32,
+    pub bar_width: f32,
+    pub bar_height: f32,
+}
+
+impl Sizes {
+    /// Every size multiplied by `factor`, except the ones relative to tiles.
+    pub fn scaled(&self, factor: f32) -> Sizes {
+        Sizes {
+            gap: self.gap * factor,
+            tight_gap: self.tight_gap * factor,
+            padding: self.padding * factor,
+            key: self.key * factor,
+            tile: self.tile * factor,
+            grid_height: self.grid_height * factor,
+            font: self.font * factor,
+            heading_font: self.heading_font * factor,
+            small_font: self.small_font * factor,
+            key_font: self.key_font * factor,
+            tile_font: self.tile_font,
+            marker_font: self.marker_font,
+            bar_width: self.bar_width * factor,
+            bar_height: self.bar_height * factor,
+        }
+    }
+}
+
+impl Theme {
+    pub fn built_in() -> Theme {
+        ron::de::from_str(BUILT_IN).expect("Built-in theme is invalid")
+    }
+
+    pub fn score_color(&self, score: LetterScore) -> Color {
+        match score {
+            LetterScore::Absent => self.absent,
+            LetterScore::Present => self.present,
+            LetterScore::Correct => self.correct,
+        }
+    }
+
+    /// The marker shown in the corner of a scored tile: filled for the right
+    /// spot, hollow for the wrong one.
+    pub fn marker(&self, score: Option<LetterScore>) -> &'static str {
+        match score {
+            Some(LetterScore::Correct) if self.markers => "●",
+            Some(LetterScore::Present) if self.markers => "○",
+            _ => "",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let theme: Theme = ron::de::from_bytes(bytes)
+                .with_context(|| format!("Invalid theme {}", load_context.path().display()))?;
+            load_context.set_default_asset(LoadedAsset::new(theme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+/// Handles to every built-in theme, and which one is chosen.
+pub struct Themes {
+    handles: Vec<Handle<Theme>>,
+    current: usize,
+    loaded: bool,
+}
+
+impl Themes {
+    /// Starts loading every theme, choosing `name` or the first one if there
+    /// is no theme by that name.
+    pub fn load(asset_server: &AssetServer, name: &str) -> Self {
+        Themes {
+            handles: THEMES
+                .iter()
+                .map(|name| asset_server.load(format!("themes/{}.theme.ron", name).as_str()))
+                .collect(),
+            current: THEMES.iter().position(|theme| *theme == name).unwrap_or(0),
+            loaded: false,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        THEMES[self.current]
+    }
+
+    /// Whether the chosen theme has been copied into the `Theme` resource yet.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// Moves on to the next theme, and returns it if it has already loaded.
+    pub fn next<'a>(&mut self, assets: &'a Assets<Theme>) -> Option<&'a Theme> {
+        self.current = (self.current + 1) % self.handles.len();
+        assets.get(&self.handles[self.current])
+    }
+}
+
+/// Copies the chosen theme into the `Theme` resource once it has loaded, and
+/// again whenever its file changes. If it fails to load, the built-in theme
+/// is used until the file is fixed.
+pub fn update_theme(
+    mut events: EventReader<AssetEvent<Theme>>,
+    assets: Res<Assets<Theme>>,
+    asset_server: Res<AssetServer>,
+    mut themes: ResMut<Themes>,
+    mut theme: ResMut<Theme>,
+) {
+    if !themes.loaded && asset_server.get_load_state(&themes.handles[themes.current]) == LoadState::Failed {
+        println!("Could not load theme {}, using the built-in one", themes.name());
+        *theme = Theme::built_in();
+        themes.loaded = true;
+    }
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle }
+                if *handle == themes.handles[themes.current] =>
+            {
+                if let Some(loaded) = assets.get(handle) {
+                    *theme = loaded.clone();
+                    themes.loaded = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Which colour of the theme a node or text takes.
+#[derive(Component, Debug, Clone, Copy)]
+pub enum Themed {
+    Background,
+    Panel,
+    Button,
+    TileText,
+    Text,
+    Absent,
+    Correct,
+}
+
+impl Themed {
+    pub fn color(&self, theme: &Theme) -> Color {
+        match self {
+            Themed::Background => theme.background,
+            Themed::Panel => theme.panel,
+            Themed::Button => theme.tile,
+            Themed::TileText => theme.tile_text,
+            Themed::Text => theme.text,
+            Themed::Absent => theme.absent,
+            Themed::Correct => theme.correct,
+        }
+    }
+}
+
+/// Colours newly spawned nodes and texts, and everything once the theme changes.
+pub fn apply_theme(
+    theme: Res<Theme>,
+    mut node_query: Query<(&Themed, &mut UiColor, ChangeTrackers<Themed>)>,
+    mut text_query: Query<(&Themed, &mut Text, ChangeTrackers<Themed>)>,
+) {
+    for (themed, mut color, tracker) in node_query.iter_mut() {
+        if theme.is_changed() || tracker.is_added() {
+            color.0 = themed.color(&theme);
+        }
+    }
+    for (themed, mut text, tracker) in text_query.iter_mut() {
+        if theme.is_changed() || tracker.is_added() {
+            for section in text.sections.iter_mut() {
+                section.style.color = themed.color(&theme);
+            }
+        }
+    }
+}